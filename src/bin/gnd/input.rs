@@ -0,0 +1,218 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use flate2::read::MultiGzDecoder;
+use pica::{Reader, ReaderBuilder};
+
+use crate::{CliError, CliResult};
+
+/// The magic number at the beginning of a gzip compressed file.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+static STDIN_CONSUMED: AtomicBool = AtomicBool::new(false);
+
+fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+fn is_glob(path: &Path) -> bool {
+    path.to_str()
+        .map(|s| s.contains(|c| matches!(c, '*' | '?' | '[')))
+        .unwrap_or_default()
+}
+
+/// Expands the input arguments into a list of files. Directories are
+/// replaced by the files they contain (recursively, in lexical order) and
+/// glob patterns by the matching files. If no input is given, the records
+/// are read from stdin (`-`).
+pub(crate) fn expand_paths(paths: &[PathBuf]) -> CliResult<Vec<PathBuf>> {
+    if paths.is_empty() {
+        return Ok(vec![PathBuf::from("-")]);
+    }
+
+    let mut result = vec![];
+
+    for path in paths {
+        if is_stdin(path) {
+            result.push(path.to_owned());
+        } else if path.is_dir() {
+            expand_dir(path, &mut result)?;
+        } else if !path.exists() && is_glob(path) {
+            let pattern = path.to_string_lossy();
+            // Like directories, hidden files are skipped unless the
+            // pattern starts with a dot.
+            let options = glob::MatchOptions {
+                require_literal_leading_dot: true,
+                ..Default::default()
+            };
+            let entries = glob::glob_with(&pattern, options).map_err(|_| {
+                CliError::Other(format!("invalid glob pattern '{}'", pattern))
+            })?;
+
+            let mut matches = vec![];
+            for entry in entries {
+                let entry =
+                    entry.map_err(|e| CliError::Other(e.to_string()))?;
+                if entry.is_dir() {
+                    expand_dir(&entry, &mut matches)?;
+                } else {
+                    matches.push(entry);
+                }
+            }
+
+            if matches.is_empty() {
+                return Err(CliError::Other(format!(
+                    "no input files match '{}'",
+                    pattern
+                )));
+            }
+
+            result.append(&mut matches);
+        } else {
+            result.push(path.to_owned());
+        }
+    }
+
+    Ok(result)
+}
+
+fn expand_dir(path: &Path, result: &mut Vec<PathBuf>) -> CliResult<()> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    entries.sort();
+
+    for entry in entries {
+        let hidden = entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.starts_with('.'))
+            .unwrap_or_default();
+
+        if hidden {
+            continue;
+        }
+
+        if entry.is_dir() {
+            expand_dir(&entry, result)?;
+        } else {
+            result.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens an input file (or stdin, if the path is `-`). Gzip compressed
+/// input is detected by its magic number and decompressed on the fly.
+pub(crate) fn open(path: &Path) -> CliResult<Box<dyn Read>> {
+    let inner: Box<dyn Read> = if is_stdin(path) {
+        if STDIN_CONSUMED.swap(true, Ordering::SeqCst) {
+            return Err(CliError::Other(
+                "stdin can't be read twice; the chosen options require \
+                 several passes over the input"
+                    .to_string(),
+            ));
+        }
+
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(path)?)
+    };
+
+    let mut reader = BufReader::new(inner);
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(MultiGzDecoder::new(reader)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// Returns a PICA+ reader of the input file.
+pub(crate) fn open_reader(
+    path: &Path,
+    skip_invalid: bool,
+) -> CliResult<Reader<Box<dyn Read>>> {
+    Ok(ReaderBuilder::new()
+        .skip_invalid(skip_invalid)
+        .from_reader(open(path)?, Some(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    /// Creates an empty temporary directory for a test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "gnd-input-{}-{}",
+            name,
+            std::process::id()
+        ));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read_to_string(path: &Path) -> String {
+        let mut content = String::new();
+        open(path).unwrap().read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn test_open() {
+        let gzip = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data/040011569.dat.gz");
+        let content = read_to_string(&gzip);
+        assert!(content.contains("003@ \x1f0040011569\x1e"));
+
+        let dir = temp_dir("open");
+        let plain = dir.join("040011569.dat");
+        File::create(&plain)
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+        assert_eq!(read_to_string(&plain), content);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_stdin_twice() {
+        STDIN_CONSUMED.store(true, Ordering::SeqCst);
+        assert!(open(Path::new("-")).is_err());
+    }
+
+    #[test]
+    fn test_expand_paths() {
+        let dir = temp_dir("expand");
+        fs::create_dir(dir.join("sub")).unwrap();
+        for name in ["b.dat", "a.dat", ".hidden.dat", "sub/c.dat"] {
+            File::create(dir.join(name)).unwrap();
+        }
+
+        assert_eq!(expand_paths(&[]).unwrap(), vec![PathBuf::from("-")]);
+
+        assert_eq!(
+            expand_paths(&[dir.clone()]).unwrap(),
+            vec![dir.join("a.dat"), dir.join("b.dat"), dir.join("sub/c.dat")]
+        );
+
+        assert_eq!(
+            expand_paths(&[dir.join("*.dat"), PathBuf::from("-")]).unwrap(),
+            vec![dir.join("a.dat"), dir.join("b.dat"), PathBuf::from("-")]
+        );
+
+        assert!(expand_paths(&[dir.join("*.xml")]).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
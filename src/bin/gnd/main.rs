@@ -6,8 +6,11 @@ use std::{fmt, io, process};
 use clap::Parser;
 
 mod cli;
+mod input;
 mod macros;
+mod pipeline;
 mod skosify;
+mod table;
 mod tabulate;
 
 use cli::{Cli, Commands};
@@ -64,7 +67,13 @@ fn main() {
     let args = Cli::parse();
 
     let config: Config = match &args.config {
-        Some(filename) => Config::from_file(filename).unwrap(),
+        Some(filename) => match Config::from_file(filename) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("gnd: {}", err);
+                process::exit(1);
+            }
+        },
         None => Config::default(),
     };
 
@@ -0,0 +1,407 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use csv::WriterBuilder;
+use gnd::{
+    AmbiguousLabels, Concept, Config, GndIdIndex, LabelIndex, SynKind,
+    UriSource,
+};
+use pica::matcher::{MatcherFlags, RecordMatcher};
+use pica::StringRecord;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::input::{expand_paths, open_reader};
+use crate::{CliError, CliResult};
+
+/// Number of records, which are read before they are processed by the
+/// worker pool.
+const BATCH_SIZE: usize = 10_000;
+
+/// Indexes, which are built in a preceding pass over the input.
+#[derive(Default)]
+struct Context {
+    gnd_ids: Option<GndIdIndex>,
+    labels: Option<LabelIndex>,
+    suppress_ambiguous: bool,
+    unique_pref_labels: bool,
+}
+
+/// Builds the concepts of all records matching the filter and the entity
+/// selector on a worker pool. The concepts are handed over to the consumer
+/// in the same order as the records occur in the input files.
+pub(crate) struct Pipeline<'a> {
+    config: &'a Config,
+    filter: RecordMatcher,
+    skip_invalid: bool,
+    pool: ThreadPool,
+}
+
+impl<'a> Pipeline<'a> {
+    pub(crate) fn new(
+        config: &'a Config,
+        filter: RecordMatcher,
+        skip_invalid: bool,
+        threads: usize,
+    ) -> CliResult<Self> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| CliError::Other(e.to_string()))?;
+
+        Ok(Self {
+            config,
+            filter,
+            skip_invalid,
+            pool,
+        })
+    }
+
+    pub(crate) fn run<F>(
+        &self,
+        paths: &[PathBuf],
+        mut consumer: F,
+    ) -> CliResult<()>
+    where
+        F: FnMut(&StringRecord, Concept) -> CliResult<()>,
+    {
+        let mut context = Context::default();
+
+        // The relations refer to their targets by IDN, so the GND-IDs of all
+        // records must be known in advance.
+        if let Some(UriSource::GndId) = self.config.concept.uri_source {
+            let mut index = GndIdIndex::new();
+            self.scan(paths, |record, _| index.add_record(record))?;
+            context.gnd_ids = Some(index);
+        }
+
+        // Whether a heading without qualifiers is ambiguous depends on the
+        // labels of all other concepts.
+        let ambiguous = match self.config.concept.strip_qualifiers {
+            Some(true) => self
+                .config
+                .concept
+                .ambiguous_labels
+                .unwrap_or(AmbiguousLabels::Suppress),
+            _ => AmbiguousLabels::Keep,
+        };
+
+        let hierarchy = self
+            .config
+            .concept
+            .corporate_body_hierarchy
+            .unwrap_or_default();
+
+        // Preferred labels, which are shared by several concepts, are made
+        // unique or reported.
+        let unique_pref_labels =
+            self.config.concept.unique_pref_labels.unwrap_or_default();
+        let pref_label_report =
+            self.config.concept.pref_label_report.as_deref();
+
+        if ambiguous != AmbiguousLabels::Keep
+            || hierarchy
+            || unique_pref_labels
+            || pref_label_report.is_some()
+        {
+            let mut index = LabelIndex::new();
+            self.build(paths, &context, false, &mut |_, concept| {
+                index.add_concept(&concept);
+                Ok(())
+            })?;
+
+            let report = self.config.concept.ambiguous_report.as_deref();
+            if ambiguous == AmbiguousLabels::Report
+                || (ambiguous == AmbiguousLabels::Suppress && report.is_some())
+            {
+                write_label_report(index.ambiguous_unqualified(), report)?;
+            }
+
+            // Without `unique_pref_labels` the preferred labels are final
+            // already; otherwise the report is written after the labels
+            // have been disambiguated.
+            if pref_label_report.is_some() && !unique_pref_labels {
+                write_label_report(
+                    index.ambiguous_preferred(),
+                    pref_label_report,
+                )?;
+            }
+
+            context.suppress_ambiguous = ambiguous == AmbiguousLabels::Suppress;
+            context.unique_pref_labels = unique_pref_labels;
+            context.labels = Some(index);
+        }
+
+        // The preferred labels, which are still shared by several concepts
+        // after the disambiguation.
+        let mut pref_labels =
+            if unique_pref_labels && pref_label_report.is_some() {
+                Some(LabelIndex::new())
+            } else {
+                None
+            };
+
+        self.build(paths, &context, true, &mut |record, concept| {
+            if let Some(index) = pref_labels.as_mut() {
+                index.add_concept(&concept);
+            }
+
+            consumer(record, concept)
+        })?;
+
+        if let Some(index) = pref_labels {
+            write_label_report(index.ambiguous_preferred(), pref_label_report)?;
+        }
+
+        Ok(())
+    }
+
+    fn build<F>(
+        &self,
+        paths: &[PathBuf],
+        context: &Context,
+        warn: bool,
+        consumer: &mut F,
+    ) -> CliResult<()>
+    where
+        F: FnMut(&StringRecord, Concept) -> CliResult<()>,
+    {
+        let paths = expand_paths(paths)?;
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+        for filename in paths.iter() {
+            let mut reader = open_reader(filename, self.skip_invalid)?;
+
+            // The position of a record is 1-based and counts the valid
+            // records only, since the reader skips invalid records silently.
+            for (index, result) in reader.records().enumerate() {
+                batch.push((filename, index + 1, result?));
+
+                if batch.len() >= BATCH_SIZE {
+                    self.process(&batch, context, warn, consumer)?;
+                    batch.clear();
+                }
+            }
+        }
+
+        self.process(&batch, context, warn, consumer)
+    }
+
+    /// Builds the concepts of all records matching the filter and the entity
+    /// selector in a preceding pass, i.e. without the label indexes and
+    /// without warnings. Invalid concepts are skipped silently, if
+    /// `skip_invalid` is set.
+    pub(crate) fn prebuild<F>(
+        &self,
+        paths: &[PathBuf],
+        mut consumer: F,
+    ) -> CliResult<()>
+    where
+        F: FnMut(&StringRecord, Concept) -> CliResult<()>,
+    {
+        self.build(paths, &Context::default(), false, &mut consumer)
+    }
+
+    /// Calls the consumer for each record without building the concepts.
+    /// The second argument tells whether the record matches the filter.
+    pub(crate) fn scan<F>(
+        &self,
+        paths: &[PathBuf],
+        mut consumer: F,
+    ) -> CliResult<()>
+    where
+        F: FnMut(&StringRecord, bool),
+    {
+        let flags = MatcherFlags::default();
+
+        for filename in expand_paths(paths)? {
+            let mut reader = open_reader(&filename, self.skip_invalid)?;
+
+            for result in reader.records() {
+                let record = result?;
+                consumer(&record, self.is_match(&record, &flags));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the record matches the filter and the entity
+    /// selector (`[concept.select]`).
+    fn is_match(&self, record: &StringRecord, flags: &MatcherFlags) -> bool {
+        self.filter.is_match(record, flags)
+            && self.config.concept.select.is_match_record(record)
+    }
+
+    fn process<F>(
+        &self,
+        batch: &[(&PathBuf, usize, StringRecord)],
+        context: &Context,
+        warn: bool,
+        consumer: &mut F,
+    ) -> CliResult<()>
+    where
+        F: FnMut(&StringRecord, Concept) -> CliResult<()>,
+    {
+        let flags = MatcherFlags::default();
+        let concepts = self.pool.install(|| {
+            batch
+                .par_iter()
+                .map(|(_, _, record)| {
+                    if self.is_match(record, &flags) {
+                        Some(Concept::from_record(record, self.config))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        for ((filename, position, record), concept) in
+            batch.iter().zip(concepts)
+        {
+            match concept {
+                Some(Ok(mut concept)) => {
+                    if let Some(gnd_ids) = context.gnd_ids.as_ref() {
+                        for relation in
+                            concept.resolve_gnd_ids(gnd_ids, self.config)
+                        {
+                            if warn {
+                                eprintln!(
+                                    "warning: unable to resolve GND-ID of \
+                                     '{}' (relation '{}' in '{}')",
+                                    relation.idn(),
+                                    relation.code(),
+                                    concept.uri()
+                                );
+                            }
+                        }
+                    }
+
+                    if let Some(labels) = context.labels.as_ref() {
+                        if context.suppress_ambiguous {
+                            concept.retain_synonyms(|synonym| {
+                                !synonym.is_unqualified()
+                                    || !labels.is_ambiguous(synonym.label())
+                            });
+                        }
+
+                        if context.unique_pref_labels {
+                            disambiguate(&mut concept, labels);
+                        }
+
+                        add_superior(&mut concept, labels);
+                    }
+
+                    consumer(record, concept)?
+                }
+                Some(Err(e)) => {
+                    let e = e.with_record_context(
+                        Some(filename.to_path_buf()),
+                        *position,
+                    );
+
+                    if !self.skip_invalid {
+                        return Err(e.into());
+                    }
+
+                    if warn {
+                        eprintln!("warning: skip invalid concept: {}", e);
+                    }
+                }
+                None => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Links a subordinate corporate body to its superior corporate body, if
+/// the heading of the superior body is the preferred or alternative label
+/// of exactly one corporate body.
+fn add_superior(concept: &mut Concept, labels: &LabelIndex) {
+    let uri = match concept
+        .superior()
+        .map(|superior| labels.get_corporate_body(superior))
+    {
+        Some([uri]) => uri.to_owned(),
+        _ => return,
+    };
+
+    concept.add_superior(&uri);
+}
+
+/// Appends the identifier of the concept to its preferred label, if the
+/// label is the preferred label of another concept as well.
+fn disambiguate(concept: &mut Concept, labels: &LabelIndex) {
+    let ambiguous = concept
+        .synset()
+        .iter()
+        .filter(|synonym| *synonym.kind() == SynKind::Preferred)
+        .any(|synonym| labels.is_ambiguous_preferred(synonym.label()));
+
+    if ambiguous {
+        concept.disambiguate_pref_label();
+    }
+}
+
+/// Writes the labels, which are shared by several concepts, as CSV to the
+/// file (or stderr).
+fn write_label_report(
+    labels: Vec<(&str, &Vec<String>)>,
+    filename: Option<&str>,
+) -> CliResult<()> {
+    let writer: Box<dyn Write> = match filename {
+        Some(filename) => Box::new(File::create(filename)?),
+        None => Box::new(io::stderr()),
+    };
+
+    let mut writer = WriterBuilder::new().from_writer(writer);
+    writer.write_record(&["label", "concepts", "uris"])?;
+
+    for (label, uris) in labels {
+        writer.write_record(&[
+            label,
+            &uris.len().to_string(),
+            &uris.join("|"),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn concept_uris(threads: usize) -> Vec<String> {
+        let config = Config::default();
+        let filter =
+            RecordMatcher::new("002@.0 =~ '^T[bfgpsu][1-7z]$'").unwrap();
+        let pipeline = Pipeline::new(&config, filter, false, threads).unwrap();
+        let paths = [Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data/dump.dat.gz")];
+
+        let mut uris = vec![];
+        pipeline
+            .run(&paths, |_, concept| {
+                uris.push(concept.uri().to_owned());
+                Ok(())
+            })
+            .unwrap();
+
+        uris
+    }
+
+    #[test]
+    fn test_order() {
+        let uris = concept_uris(1);
+        assert_eq!(uris.len(), 10);
+        assert_eq!(concept_uris(4), uris);
+    }
+}
@@ -1,24 +1,37 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use clap::Parser;
-use gnd::{Collection, Concept, Config, RelationKind, SynKind};
-use pica::matcher::{MatcherFlags, RecordMatcher};
-use pica::ReaderBuilder;
+use csv::WriterBuilder;
+use gnd::{
+    Collection, Concept, ConceptKind, Config, DanglingRelations, LinkPredicate,
+    OutputProfile, RdfFormat, Relation, RelationIndex, SynKind, Synonym,
+};
+use pica::matcher::RecordMatcher;
 use sophia::graph::inmem::LightGraph;
-use sophia::graph::MutableGraph;
+use sophia::graph::{Graph, MutableGraph};
 use sophia::iri::Iri;
 use sophia::ns::rdf;
 use sophia::prefix::Prefix;
+use sophia::serializer::jsonld::JsonLdSerializer;
+use sophia::serializer::nt::NtSerializer;
 use sophia::serializer::turtle::{TurtleConfig, TurtleSerializer};
+use sophia::serializer::xml::RdfXmlSerializer;
 use sophia::term::literal::Literal;
-use sophia_api::serializer::TripleSerializer;
+use sophia_api::serializer::{QuadSerializer, TripleSerializer};
 
+use crate::pipeline::Pipeline;
 use crate::{cli_flag, cli_option, CliError, CliResult};
 
 const DEFAULT_FILTER: &str = "002@.0 =~ '^T[bfgpsu][1-7z]$'";
 
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_DATE: &str = "http://www.w3.org/2001/XMLSchema#date";
+const XSD_GYEAR: &str = "http://www.w3.org/2001/XMLSchema#gYear";
+
 macro_rules! prefix {
     ($prefix:expr, $uri:expr) => {
         (Prefix::new_unchecked($prefix), Iri::new_unchecked($uri))
@@ -33,10 +46,54 @@ pub(crate) mod skos {
         prefLabel,
         altLabel,
         hiddenLabel,
-        broader,
-        narrower,
-        related,
-        member
+        member,
+        exactMatch
+    );
+}
+
+pub(crate) mod skosxl {
+    namespace!(
+        "http://www.w3.org/2008/05/skos-xl#",
+        Label,
+        prefLabel,
+        altLabel,
+        hiddenLabel,
+        literalForm
+    );
+}
+
+pub(crate) mod dct {
+    namespace!("http://purl.org/dc/terms/", source);
+}
+
+pub(crate) mod owl {
+    namespace!("http://www.w3.org/2002/07/owl#", sameAs);
+}
+
+pub(crate) mod gndo {
+    namespace!(
+        "https://d-nb.info/standards/elementset/gnd#",
+        DifferentiatedPerson,
+        CorporateBody,
+        ConferenceOrEvent,
+        PlaceOrGeographicName,
+        SubjectHeading,
+        Work,
+        preferredNameForThePerson,
+        variantNameForThePerson,
+        preferredNameForTheCorporateBody,
+        variantNameForTheCorporateBody,
+        preferredNameForTheConferenceOrEvent,
+        variantNameForTheConferenceOrEvent,
+        preferredNameForThePlaceOrGeographicName,
+        variantNameForThePlaceOrGeographicName,
+        preferredNameForTheSubjectHeading,
+        variantNameForTheSubjectHeading,
+        preferredNameForTheWork,
+        variantNameForTheWork,
+        dateOfBirth,
+        dateOfDeath,
+        periodOfActivity
     );
 }
 
@@ -50,32 +107,517 @@ pub(crate) struct SkosifyArgs {
     pub(crate) filter: Option<String>,
 
     #[clap(
-        help = "Whether to skip invalid PICA+ records or not.",
+        help = "Whether to skip invalid PICA+ records and concepts or not.",
         long,
         short
     )]
     pub(crate) skip_invalid: bool,
 
+    #[clap(
+        help = "Write the triples of each concept as soon as it is built \
+                (N-Triples only).",
+        long
+    )]
+    pub(crate) streaming: bool,
+
+    #[clap(help = "Output format (ntriples, turtle, rdfxml or jsonld).", long)]
+    pub(crate) format: Option<String>,
+
+    #[clap(
+        help = "Output profile (skos or gndo). The gndo profile uses the \
+                classes and properties of the GND Ontology.",
+        long
+    )]
+    pub(crate) profile: Option<String>,
+
+    #[clap(help = "Emit SKOS-XL label resources instead of literals.", long)]
+    pub(crate) skosxl: bool,
+
+    #[clap(
+        help = "Add inverse relations (narrower, related) between concepts.",
+        long
+    )]
+    pub(crate) inverse_relations: bool,
+
+    #[clap(
+        help = "How to handle relations to unknown concepts (keep, drop or \
+                report).",
+        long
+    )]
+    pub(crate) dangling_relations: Option<String>,
+
+    #[clap(
+        help = "Write a summary of the relations to unknown concepts to a \
+                CSV file.",
+        long
+    )]
+    pub(crate) dangling_report: Option<String>,
+
+    #[clap(help = "Number of threads used to build the concepts.", long)]
+    pub(crate) threads: Option<usize>,
+
     #[clap(long, short)]
     pub(crate) output: Option<String>,
 
-    #[clap(required = false, parse(from_os_str))]
+    #[clap(
+        help = "Input files, directories or glob patterns; plain or gzip \
+                compressed. Reads from stdin if no input or '-' is given.",
+        required = false,
+        parse(from_os_str)
+    )]
     pub(crate) paths: Vec<PathBuf>,
 }
 
+enum GraphSerializer {
+    NTriples(NtSerializer<Box<dyn Write>>),
+    Turtle(TurtleSerializer<Box<dyn Write>>),
+    RdfXml(RdfXmlSerializer<Box<dyn Write>>),
+    JsonLd(JsonLdSerializer<Box<dyn Write>>),
+}
+
+impl GraphSerializer {
+    fn new(
+        format: RdfFormat,
+        writer: Box<dyn Write>,
+        config: &Config,
+    ) -> GraphSerializer {
+        match format {
+            RdfFormat::NTriples => Self::NTriples(NtSerializer::new(writer)),
+            RdfFormat::Turtle => {
+                let gnd_uri = config.concept.base_uri.to_string();
+                let turtle_config = TurtleConfig::new()
+                    .with_pretty(config.skosify.pretty)
+                    .with_prefix_map(
+                        &[
+                            prefix!(
+                                "rdf",
+                                "http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                            ),
+                            prefix!(
+                                "skos",
+                                "http://www.w3.org/2004/02/skos/core#"
+                            ),
+                            prefix!(
+                                "skosxl",
+                                "http://www.w3.org/2008/05/skos-xl#"
+                            ),
+                            prefix!("dct", "http://purl.org/dc/terms/"),
+                            prefix!("owl", "http://www.w3.org/2002/07/owl#"),
+                            prefix!(
+                                "gndo",
+                                "https://d-nb.info/standards/elementset/gnd#"
+                            ),
+                            prefix!("gnd", &gnd_uri),
+                        ][..],
+                    );
+
+                Self::Turtle(TurtleSerializer::new_with_config(
+                    writer,
+                    turtle_config,
+                ))
+            }
+            RdfFormat::RdfXml => Self::RdfXml(RdfXmlSerializer::new(writer)),
+            RdfFormat::JsonLd => Self::JsonLd(JsonLdSerializer::new(writer)),
+        }
+    }
+
+    fn serialize(&mut self, graph: &LightGraph) -> CliResult<()> {
+        let result = match self {
+            Self::NTriples(ser) => ser
+                .serialize_graph(graph)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Self::Turtle(ser) => ser
+                .serialize_graph(graph)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Self::RdfXml(ser) => ser
+                .serialize_graph(graph)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Self::JsonLd(ser) => ser
+                .serialize_dataset(&graph.as_dataset())
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+        };
+
+        result.map_err(CliError::Other)
+    }
+}
+
+fn add_concept(
+    graph: &mut LightGraph,
+    concept: &Concept,
+    config: &Config,
+    profile: OutputProfile,
+    skosxl: bool,
+) {
+    match profile {
+        OutputProfile::Skos => add_skos_concept(graph, concept, config, skosxl),
+        OutputProfile::Gndo => add_gndo_concept(graph, concept, config),
+    }
+
+    for relation in concept.relations() {
+        add_relation(graph, concept.uri(), relation, config, profile);
+    }
+
+    // Identifiers of systems without an URI template are skipped, as well
+    // as identifiers, whose value doesn't yield a valid IRI (e.g. values
+    // with spaces).
+    let subj = Iri::new(concept.uri()).unwrap();
+    for identifier in concept.identifiers() {
+        if let Some(uri) = identifier.uri(config) {
+            let obj = match Iri::new(uri.as_str()) {
+                Ok(obj) => obj,
+                Err(_) => {
+                    eprintln!(
+                        "warning: skip invalid identifier IRI '{}' of '{}'",
+                        uri,
+                        concept.uri()
+                    );
+                    continue;
+                }
+            };
+
+            match config.identifier.predicate {
+                Some(LinkPredicate::SameAs) => {
+                    graph.insert(&subj, &owl::sameAs, &obj).unwrap();
+                }
+                _ => {
+                    graph.insert(&subj, &skos::exactMatch, &obj).unwrap();
+                }
+            }
+        }
+    }
+}
+
+fn label_literal(synonym: &Synonym, config: &Config) -> Literal<Box<str>> {
+    let language_tag = synonym
+        .language_tag()
+        .unwrap_or(config.skosify.language_tag.as_str());
+
+    Literal::<Box<str>>::new_lang(synonym.label(), language_tag).unwrap()
+}
+
+/// Returns the lexical form and the XSD datatype of a date of birth or
+/// death: `xsd:gYear` for a year ("1886") and `xsd:date` for a complete
+/// date ("1886-11-01" or "01.11.1886"). Other values (e.g. "ca. 1500")
+/// have no specific datatype.
+fn typed_date(date: &str) -> Option<(String, &'static str)> {
+    let is_number = |value: &str, len: usize| {
+        value.len() == len && value.bytes().all(|b| b.is_ascii_digit())
+    };
+
+    if is_number(date, 4) {
+        return Some((date.to_string(), XSD_GYEAR));
+    }
+
+    let (year, month, day) = match date.split('-').collect::<Vec<_>>()[..] {
+        [year, month, day] => (year, month, day),
+        _ => match date.split('.').collect::<Vec<_>>()[..] {
+            [day, month, year] => (year, month, day),
+            _ => return None,
+        },
+    };
+
+    if !is_number(year, 4) || !is_number(month, 2) || !is_number(day, 2) {
+        return None;
+    }
+
+    // The strings consist of digits only, so they can be parsed.
+    let (year, month, day) = (
+        year.parse::<u32>().unwrap(),
+        month.parse::<u32>().unwrap(),
+        day.parse::<u32>().unwrap(),
+    );
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap_year => 29,
+        2 => 28,
+        _ => 0,
+    };
+
+    if (1..=days).contains(&day) {
+        Some((format!("{:04}-{:02}-{:02}", year, month, day), XSD_DATE))
+    } else {
+        None
+    }
+}
+
+fn date_literal(date: &str) -> Literal<Box<str>> {
+    match typed_date(date) {
+        Some((value, datatype)) => {
+            Literal::<Box<str>>::new_dt(value, Iri::new_unchecked(datatype))
+        }
+        None => {
+            Literal::<Box<str>>::new_dt(date, Iri::new_unchecked(XSD_STRING))
+        }
+    }
+}
+
+fn add_skos_concept(
+    graph: &mut LightGraph,
+    concept: &Concept,
+    config: &Config,
+    skosxl: bool,
+) {
+    let subj = Iri::new(concept.uri()).unwrap();
+    graph.insert(&subj, &rdf::type_, &skos::Concept).unwrap();
+
+    for synonym in concept.synset() {
+        let literal = label_literal(synonym, config);
+
+        if skosxl {
+            let (pred, name) = match *synonym.kind() {
+                SynKind::Preferred => (&skosxl::prefLabel, "pref"),
+                SynKind::Alternative => (&skosxl::altLabel, "alt"),
+                SynKind::Hidden => (&skosxl::hiddenLabel, "hidden"),
+            };
+
+            // The label URI only depends on the concept URI, the kind and
+            // the label, so that it remains stable between runs.
+            let label = Iri::new(format!(
+                "{}#{}-{:016x}",
+                concept.uri(),
+                name,
+                synonym.fingerprint()
+            ))
+            .unwrap();
+
+            graph.insert(&subj, pred, &label).unwrap();
+            graph.insert(&label, &rdf::type_, &skosxl::Label).unwrap();
+            graph
+                .insert(&label, &skosxl::literalForm, &literal)
+                .unwrap();
+
+            if let Some(source) = synonym.source() {
+                graph.insert(&label, &dct::source, source).unwrap();
+            }
+
+            continue;
+        }
+
+        match *synonym.kind() {
+            SynKind::Preferred => {
+                graph.insert(&subj, &skos::prefLabel, &literal).unwrap();
+            }
+            SynKind::Alternative => {
+                graph.insert(&subj, &skos::altLabel, &literal).unwrap();
+            }
+            SynKind::Hidden => {
+                graph.insert(&subj, &skos::hiddenLabel, &literal).unwrap();
+            }
+        }
+    }
+}
+
+/// Describes the concept with the classes and properties of the GND
+/// Ontology. Hidden labels have no counterpart in the ontology and are
+/// omitted.
+fn add_gndo_concept(
+    graph: &mut LightGraph,
+    concept: &Concept,
+    config: &Config,
+) {
+    let subj = Iri::new(concept.uri()).unwrap();
+
+    let (class, preferred, variant) = match concept.kind() {
+        ConceptKind::Person => (
+            &gndo::DifferentiatedPerson,
+            &gndo::preferredNameForThePerson,
+            &gndo::variantNameForThePerson,
+        ),
+        ConceptKind::CorporateBody => (
+            &gndo::CorporateBody,
+            &gndo::preferredNameForTheCorporateBody,
+            &gndo::variantNameForTheCorporateBody,
+        ),
+        ConceptKind::Conference => (
+            &gndo::ConferenceOrEvent,
+            &gndo::preferredNameForTheConferenceOrEvent,
+            &gndo::variantNameForTheConferenceOrEvent,
+        ),
+        ConceptKind::Place => (
+            &gndo::PlaceOrGeographicName,
+            &gndo::preferredNameForThePlaceOrGeographicName,
+            &gndo::variantNameForThePlaceOrGeographicName,
+        ),
+        ConceptKind::SubjectTerm => (
+            &gndo::SubjectHeading,
+            &gndo::preferredNameForTheSubjectHeading,
+            &gndo::variantNameForTheSubjectHeading,
+        ),
+        ConceptKind::Work => (
+            &gndo::Work,
+            &gndo::preferredNameForTheWork,
+            &gndo::variantNameForTheWork,
+        ),
+    };
+
+    graph.insert(&subj, &rdf::type_, class).unwrap();
+
+    for synonym in concept.synset() {
+        let pred = match *synonym.kind() {
+            SynKind::Preferred => preferred,
+            SynKind::Alternative => variant,
+            SynKind::Hidden => continue,
+        };
+
+        graph
+            .insert(&subj, pred, &label_literal(synonym, config))
+            .unwrap();
+    }
+
+    if let Some(data) = concept.biographical() {
+        if let Some(date) = data.date_of_birth() {
+            graph
+                .insert(&subj, &gndo::dateOfBirth, &date_literal(date))
+                .unwrap();
+        }
+
+        if let Some(date) = data.date_of_death() {
+            graph
+                .insert(&subj, &gndo::dateOfDeath, &date_literal(date))
+                .unwrap();
+        }
+
+        // The period of activity is a span ("1900-1950"), which has no
+        // XSD datatype.
+        let period = match (data.activity_start(), data.activity_end()) {
+            (Some(from), Some(to)) => Some(format!("{}-{}", from, to)),
+            (Some(from), None) => Some(format!("{}-", from)),
+            (None, Some(to)) => Some(format!("-{}", to)),
+            (None, None) => None,
+        };
+
+        if let Some(period) = period {
+            graph
+                .insert(&subj, &gndo::periodOfActivity, period.as_str())
+                .unwrap();
+        }
+    }
+}
+
+fn add_relation(
+    graph: &mut LightGraph,
+    subject: &str,
+    relation: &Relation,
+    config: &Config,
+    profile: OutputProfile,
+) {
+    let predicate = match profile {
+        OutputProfile::Skos => relation.predicate(config),
+        OutputProfile::Gndo => relation.gndo_predicate(config),
+    };
+
+    // The predicates of the `[relation]` mapping are validated in `run`.
+    let subj = Iri::new(subject).unwrap();
+    let pred = Iri::new(predicate).unwrap();
+    let obj = Iri::new(relation.uri()).unwrap();
+    graph.insert(&subj, &pred, &obj).unwrap();
+}
+
+/// Number of relations and the distinct targets of relations to unknown
+/// concepts, grouped by the source field tag and the relation code.
+type DanglingSummary = BTreeMap<(String, String), (usize, HashSet<String>)>;
+
+fn add_dangling(summary: &mut DanglingSummary, relation: &Relation) {
+    let (count, targets) = summary
+        .entry((relation.field().to_owned(), relation.code().to_owned()))
+        .or_insert_with(|| (0, HashSet::new()));
+
+    *count += 1;
+    targets.insert(relation.uri().to_owned());
+}
+
+fn write_dangling_report(
+    summary: &DanglingSummary,
+    filename: Option<&str>,
+) -> CliResult<()> {
+    let writer: Box<dyn Write> = match filename {
+        Some(filename) => Box::new(File::create(filename)?),
+        None => Box::new(io::stderr()),
+    };
+
+    let mut writer = WriterBuilder::new().from_writer(writer);
+    writer.write_record(&["field", "code", "relations", "targets"])?;
+
+    for ((field, code), (count, targets)) in summary.iter() {
+        writer.write_record(&[
+            field,
+            code,
+            &count.to_string(),
+            &targets.len().to_string(),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn add_collections(graph: &mut LightGraph, collections: &[Collection]) {
+    for collection in collections {
+        for (uri, members) in collection.collections() {
+            let subj = Iri::new(uri.as_str()).unwrap();
+            graph.insert(&subj, &rdf::type_, &skos::Collection).unwrap();
+
+            for member in members {
+                let obj = Iri::new(member.as_str()).unwrap();
+                graph.insert(&subj, &skos::member, &obj).unwrap();
+            }
+        }
+    }
+}
+
 pub(crate) fn run(config: &Config, args: &SkosifyArgs) -> CliResult<()> {
+    config.relation.validate()?;
+
     let mut collections = config
         .collections
         .iter()
-        .filter_map(|c| Collection::try_from(c).ok())
-        .collect::<Vec<Collection>>();
+        .map(Collection::try_from)
+        .collect::<Result<Vec<Collection>, _>>()?;
 
     let filter_str =
         cli_option!(args.filter, config.concept.filter, DEFAULT_FILTER);
     let skip_invalid =
         cli_flag!(args.skip_invalid, config.concept.skip_invalid);
+    let streaming = cli_flag!(args.streaming, config.skosify.streaming);
+    let skosxl = cli_flag!(args.skosxl, config.skosify.skosxl);
+    let inverse_relations =
+        cli_flag!(args.inverse_relations, config.skosify.inverse_relations);
+    let threads = args.threads.or(config.concept.threads).unwrap_or(1);
+    let dangling = match &args.dangling_relations {
+        Some(mode) => DanglingRelations::from_str(mode)?,
+        None => config
+            .skosify
+            .dangling_relations
+            .unwrap_or(DanglingRelations::Keep),
+    };
+    let dangling_report = args
+        .dangling_report
+        .as_deref()
+        .or(config.skosify.dangling_report.as_deref());
+    let profile = match &args.profile {
+        Some(profile) => OutputProfile::from_str(profile)?,
+        None => config.skosify.profile.unwrap_or(OutputProfile::Skos),
+    };
+    let format = match &args.format {
+        Some(format) => RdfFormat::from_str(format)?,
+        None => config.skosify.format.unwrap_or(RdfFormat::Turtle),
+    };
+
+    // Only N-Triples can be written in several chunks; Turtle would repeat
+    // the prefixes for each chunk and RDF/XML and JSON-LD documents have a
+    // single root.
+    if streaming && format != RdfFormat::NTriples {
+        return Err(CliError::Other(
+            "streaming is only supported by N-Triples".to_string(),
+        ));
+    }
 
-    let matcher_flags = MatcherFlags::default();
     let filter = match RecordMatcher::new(&filter_str) {
         Ok(f) => f,
         Err(_) => {
@@ -87,112 +629,172 @@ pub(crate) fn run(config: &Config, args: &SkosifyArgs) -> CliResult<()> {
     };
 
     let writer: Box<dyn Write> = match &args.output {
-        Some(filename) => Box::new(File::create(filename)?),
-        None => Box::new(io::stdout()),
+        Some(filename) => Box::new(BufWriter::new(File::create(filename)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
     };
 
+    let mut ser = GraphSerializer::new(format, writer, config);
     let mut graph = LightGraph::new();
+    let mut index = RelationIndex::new();
+    let mut summary = DanglingSummary::new();
 
-    for filename in &args.paths {
-        let mut reader = ReaderBuilder::new()
-            .skip_invalid(skip_invalid)
-            .from_path(filename)?;
+    let pipeline = Pipeline::new(config, filter, skip_invalid, threads)?;
 
-        for result in reader.records() {
-            let record = result?;
+    // In order to drop relations to unknown concepts while streaming, the
+    // URIs of all concepts must be known in advance. If invalid concepts
+    // are skipped, only the concepts, which can be built, are known.
+    if dangling == DanglingRelations::Drop {
+        if skip_invalid {
+            pipeline.prebuild(&args.paths, |record, _| {
+                index.add_record(record, config);
+                Ok(())
+            })?;
+        } else {
+            pipeline.scan(&args.paths, |record, is_match| {
+                if is_match {
+                    index.add_record(record, config);
+                }
+            })?;
+        }
+    }
 
-            if filter.is_match(&record, &matcher_flags) {
-                let concept = Concept::from_record(&record, config)?;
-                let subj = Iri::new(concept.uri()).unwrap();
+    pipeline.run(&args.paths, |record, mut concept| {
+        for collection in collections.iter_mut() {
+            collection.add_record(record, config)
+        }
 
-                for collection in collections.iter_mut() {
-                    collection.add_record(&record, config)
+        if dangling == DanglingRelations::Drop {
+            concept.retain_relations(|relation| {
+                let known = index.contains(relation.uri());
+                if !known {
+                    add_dangling(&mut summary, relation);
                 }
 
-                graph.insert(&subj, &rdf::type_, &skos::Concept).unwrap();
-
-                for synonym in concept.synset() {
-                    let literal = Literal::<Box<str>>::new_lang(
-                        synonym.label(),
-                        config.skosify.language_tag.as_str(),
-                    )
-                    .unwrap();
-                    match *synonym.kind() {
-                        SynKind::Preferred => {
-                            graph
-                                .insert(&subj, &skos::prefLabel, &literal)
-                                .unwrap();
-                        }
-                        SynKind::Alternative => {
-                            graph
-                                .insert(&subj, &skos::altLabel, &literal)
-                                .unwrap();
-                        }
-                        SynKind::Hidden => {
-                            graph
-                                .insert(&subj, &skos::hiddenLabel, &literal)
-                                .unwrap();
-                        }
-                    }
-                }
+                known
+            });
+        }
 
-                for relation in concept.relations() {
-                    let uri = Iri::new(relation.uri()).unwrap();
-                    match *relation.kind() {
-                        RelationKind::Broader => {
-                            graph.insert(&subj, &skos::broader, &uri).unwrap();
-                        }
-                        RelationKind::Narrower => {
-                            graph.insert(&subj, &skos::narrower, &uri).unwrap();
-                        }
-                        RelationKind::Related => {
-                            graph.insert(&subj, &skos::related, &uri).unwrap();
-                        }
-                    }
-                }
-            }
+        if inverse_relations || dangling == DanglingRelations::Report {
+            index.add_concept(&concept);
         }
-    }
 
-    for collection in collections {
-        for (uri, members) in collection.items().iter() {
-            let length = members.len();
+        if streaming {
+            // Each concept is serialized on its own, so the memory
+            // consumption doesn't depend on the size of the input.
+            let mut graph = LightGraph::new();
+            add_concept(&mut graph, &concept, config, profile, skosxl);
+            ser.serialize(&graph)?;
+        } else {
+            add_concept(&mut graph, &concept, config, profile, skosxl);
+        }
 
-            if let Some(minimum) = collection.minimum() {
-                if length < *minimum {
-                    continue;
-                }
-            }
+        Ok(())
+    })?;
 
-            if let Some(maximum) = collection.maximum() {
-                if length > *maximum {
-                    continue;
-                }
+    if inverse_relations {
+        for (subject, relation) in index.inverse_relations() {
+            add_relation(&mut graph, &subject, &relation, config, profile);
+        }
+
+        // Relations to unknown concepts are summarized in the report mode.
+        if dangling == DanglingRelations::Keep {
+            for (subject, relation) in index.dangling_relations() {
+                eprintln!(
+                    "warning: unknown target '{}' of relation '{}' in '{}'",
+                    relation.uri(),
+                    relation.code(),
+                    subject
+                );
             }
+        }
+    }
 
-            let subj = Iri::new(uri).unwrap();
-            graph.insert(&subj, &rdf::type_, &skos::Collection).unwrap();
+    add_collections(&mut graph, &collections);
+    ser.serialize(&graph)?;
 
-            for member in members {
-                let obj = Iri::new(member).unwrap();
-                graph.insert(&subj, &skos::member, &obj).unwrap();
+    match dangling {
+        DanglingRelations::Report => {
+            for (_, relation) in index.dangling_relations() {
+                add_dangling(&mut summary, relation);
             }
+
+            write_dangling_report(&summary, dangling_report)?;
+        }
+        DanglingRelations::Drop if dangling_report.is_some() => {
+            write_dangling_report(&summary, dangling_report)?;
         }
+        _ => (),
     }
 
-    let gnd_uri = config.concept.base_uri.to_string();
-    let config = TurtleConfig::new()
-        .with_pretty(config.skosify.pretty)
-        .with_prefix_map(
-            &[
-                prefix!("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
-                prefix!("skos", "http://www.w3.org/2004/02/skos/core#"),
-                prefix!("gnd", &gnd_uri),
-            ][..],
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use std::fs::{read_to_string, remove_file};
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_typed_date() {
+        assert_eq!(typed_date("1886"), Some(("1886".to_string(), XSD_GYEAR)));
+        assert_eq!(
+            typed_date("1886-11-01"),
+            Some(("1886-11-01".to_string(), XSD_DATE))
+        );
+        assert_eq!(
+            typed_date("01.11.1886"),
+            Some(("1886-11-01".to_string(), XSD_DATE))
         );
+        assert_eq!(typed_date("ca. 1500"), None);
+        assert_eq!(typed_date("1886-13-01"), None);
+        assert_eq!(typed_date("31.02.1900"), None);
+        assert_eq!(typed_date("29.02.1900"), None);
+        assert_eq!(
+            typed_date("29.02.2000"),
+            Some(("2000-02-29".to_string(), XSD_DATE))
+        );
+        assert_eq!(typed_date("1886-04-31"), None);
+        assert_eq!(typed_date("886"), None);
+    }
 
-    let mut ser = TurtleSerializer::new_with_config(writer, config);
-    ser.serialize_graph(&graph).unwrap();
+    #[test]
+    fn test_run_streaming() {
+        let config = Config::default();
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+        let filename = std::env::temp_dir()
+            .join(format!("gnd-skosify-streaming-{}.nt", std::process::id()));
 
-    Ok(())
+        let args = SkosifyArgs::parse_from([
+            OsStr::new("skosify"),
+            OsStr::new("--streaming"),
+            OsStr::new("--format"),
+            OsStr::new("turtle"),
+            dir.join("040011569.dat.gz").as_os_str(),
+        ]);
+        assert!(run(&config, &args).is_err());
+
+        let args = SkosifyArgs::parse_from([
+            OsStr::new("skosify"),
+            OsStr::new("--streaming"),
+            OsStr::new("--format"),
+            OsStr::new("ntriples"),
+            OsStr::new("--output"),
+            filename.as_os_str(),
+            dir.join("040011569.dat.gz").as_os_str(),
+            dir.join("040181189.dat.gz").as_os_str(),
+        ]);
+        run(&config, &args).unwrap();
+
+        let content = read_to_string(&filename).unwrap();
+        remove_file(&filename).unwrap();
+
+        assert!(content.lines().all(|line| line.ends_with(" .")));
+        for idn in ["040011569", "040181189"] {
+            let subject = format!("<http://d-nb.info/gnd/{}> ", idn);
+            assert!(content.lines().any(|line| line.starts_with(&subject)));
+        }
+    }
 }
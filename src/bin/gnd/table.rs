@@ -0,0 +1,373 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use csv::WriterBuilder;
+use gnd::{QuoteStyle, TableFormat};
+use parquet::arrow::ArrowWriter;
+
+use crate::{CliError, CliResult};
+
+/// Number of rows, which are collected before they are written as a record
+/// batch to an Arrow or Parquet file.
+const BATCH_ROWS: usize = 65_536;
+
+pub(crate) struct TableOptions {
+    pub(crate) format: TableFormat,
+    pub(crate) delimiter: Option<char>,
+    pub(crate) quote_style: QuoteStyle,
+    pub(crate) header: bool,
+}
+
+/// Writes the rows of a table either as CSV/TSV, as JSON Lines or in one
+/// of the columnar formats (Arrow IPC, Parquet). All columns are strings.
+pub(crate) enum TableWriter {
+    Csv {
+        writer: csv::Writer<Box<dyn Write>>,
+        header: bool,
+    },
+    JsonLines {
+        writer: Box<dyn Write>,
+        columns: Vec<String>,
+    },
+    Columnar {
+        sink: Option<Box<dyn Write>>,
+        format: TableFormat,
+        schema: Option<SchemaRef>,
+        encoder: Option<BatchEncoder>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+pub(crate) enum BatchEncoder {
+    Arrow(FileWriter<Box<dyn Write>>),
+    Parquet(ArrowWriter<Box<dyn Write>>),
+}
+
+fn other_error<E: ToString>(err: E) -> CliError {
+    CliError::Other(err.to_string())
+}
+
+impl TableWriter {
+    pub(crate) fn new(
+        writer: Box<dyn Write>,
+        options: &TableOptions,
+    ) -> CliResult<Self> {
+        match options.format {
+            TableFormat::Csv | TableFormat::Tsv => {
+                let delimiter = match options.delimiter {
+                    Some(c) if c.is_ascii() => c as u8,
+                    Some(c) => {
+                        return Err(gnd::Error::InvalidValue {
+                            option: "delimiter".to_string(),
+                            value: c.to_string(),
+                        }
+                        .into())
+                    }
+                    None if options.format == TableFormat::Tsv => b'\t',
+                    None => b',',
+                };
+
+                let quote_style = match options.quote_style {
+                    QuoteStyle::Always => csv::QuoteStyle::Always,
+                    QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+                    QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+                    QuoteStyle::Never => csv::QuoteStyle::Never,
+                };
+
+                Ok(Self::Csv {
+                    writer: WriterBuilder::new()
+                        .delimiter(delimiter)
+                        .quote_style(quote_style)
+                        .from_writer(writer),
+                    header: options.header,
+                })
+            }
+            TableFormat::JsonLines => Ok(Self::JsonLines {
+                writer,
+                columns: vec![],
+            }),
+            TableFormat::Arrow | TableFormat::Parquet => Ok(Self::Columnar {
+                sink: Some(writer),
+                format: options.format,
+                schema: None,
+                encoder: None,
+                rows: vec![],
+            }),
+        }
+    }
+
+    /// Writes the column names. JSON Lines and the columnar formats always
+    /// need the column names, even if the header row is suppressed.
+    pub(crate) fn write_header(&mut self, columns: &[&str]) -> CliResult<()> {
+        match self {
+            Self::Csv { writer, header } => {
+                if *header {
+                    writer.write_record(columns)?;
+                }
+            }
+            Self::JsonLines { columns: names, .. } => {
+                *names = columns.iter().map(ToString::to_string).collect();
+            }
+            Self::Columnar {
+                sink,
+                format,
+                schema,
+                encoder,
+                ..
+            } => {
+                let fields = columns
+                    .iter()
+                    .map(|name| Field::new(name, DataType::Utf8, false))
+                    .collect::<Vec<_>>();
+                let new_schema = Arc::new(Schema::new(fields));
+                let sink = sink.take().unwrap();
+
+                *encoder = Some(match format {
+                    TableFormat::Arrow => BatchEncoder::Arrow(
+                        FileWriter::try_new(sink, &new_schema)
+                            .map_err(other_error)?,
+                    ),
+                    _ => BatchEncoder::Parquet(
+                        ArrowWriter::try_new(sink, new_schema.clone(), None)
+                            .map_err(other_error)?,
+                    ),
+                });
+                *schema = Some(new_schema);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn write_record(&mut self, record: &[&str]) -> CliResult<()> {
+        match self {
+            Self::Csv { writer, .. } => writer.write_record(record)?,
+            Self::JsonLines { writer, columns } => {
+                // The object is written by hand, because `serde_json::Map`
+                // orders the keys alphabetically instead of by column.
+                writer.write_all(b"{")?;
+
+                for (i, (name, value)) in columns.iter().zip(record).enumerate()
+                {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                    }
+
+                    serde_json::to_writer(&mut *writer, name)
+                        .map_err(other_error)?;
+                    writer.write_all(b":")?;
+                    serde_json::to_writer(&mut *writer, value)
+                        .map_err(other_error)?;
+                }
+
+                writer.write_all(b"}\n")?;
+            }
+            Self::Columnar { rows, .. } => {
+                rows.push(record.iter().map(ToString::to_string).collect());
+
+                if rows.len() >= BATCH_ROWS {
+                    self.write_batch()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the buffered rows of a columnar table as a record batch.
+    fn write_batch(&mut self) -> CliResult<()> {
+        if let Self::Columnar {
+            schema: Some(schema),
+            encoder: Some(encoder),
+            rows,
+            ..
+        } = self
+        {
+            if rows.is_empty() {
+                return Ok(());
+            }
+
+            let columns = (0..schema.fields().len())
+                .map(|i| {
+                    let values = rows
+                        .iter()
+                        .map(|row| row[i].as_str())
+                        .collect::<Vec<&str>>();
+
+                    Arc::new(StringArray::from(values)) as ArrayRef
+                })
+                .collect::<Vec<_>>();
+
+            let batch = RecordBatch::try_new(schema.clone(), columns)
+                .map_err(other_error)?;
+
+            match encoder {
+                BatchEncoder::Arrow(writer) => {
+                    writer.write(&batch).map_err(other_error)?
+                }
+                BatchEncoder::Parquet(writer) => {
+                    writer.write(&batch).map_err(other_error)?
+                }
+            }
+
+            rows.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Writes all buffered rows and the footer of the columnar formats.
+    pub(crate) fn finish(mut self) -> CliResult<()> {
+        self.write_batch()?;
+
+        match self {
+            Self::Csv { mut writer, .. } => writer.flush()?,
+            Self::JsonLines { mut writer, .. } => writer.flush()?,
+            Self::Columnar {
+                encoder: Some(encoder),
+                ..
+            } => match encoder {
+                BatchEncoder::Arrow(mut writer) => {
+                    writer.finish().map_err(other_error)?
+                }
+                BatchEncoder::Parquet(writer) => {
+                    writer.close().map(|_| ()).map_err(other_error)?
+                }
+            },
+            Self::Columnar { .. } => (),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::path::PathBuf;
+
+    use arrow::ipc::reader::FileReader;
+
+    use super::*;
+
+    fn options(format: TableFormat) -> TableOptions {
+        TableOptions {
+            format,
+            delimiter: None,
+            quote_style: QuoteStyle::Necessary,
+            header: true,
+        }
+    }
+
+    /// Writes the rows to a temporary file and returns its path.
+    fn write_table(
+        name: &str,
+        options: &TableOptions,
+        rows: &[[&str; 2]],
+    ) -> PathBuf {
+        let filename = std::env::temp_dir().join(format!(
+            "gnd-table-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let mut writer = TableWriter::new(
+            Box::new(File::create(&filename).unwrap()),
+            options,
+        )
+        .unwrap();
+
+        writer.write_header(&["uri", "label"]).unwrap();
+        for row in rows {
+            writer.write_record(row).unwrap();
+        }
+
+        writer.finish().unwrap();
+        filename
+    }
+
+    fn read_table(
+        name: &str,
+        options: &TableOptions,
+        rows: &[[&str; 2]],
+    ) -> String {
+        let filename = write_table(name, options, rows);
+        let content = fs::read_to_string(&filename).unwrap();
+        fs::remove_file(&filename).unwrap();
+        content
+    }
+
+    const ROWS: [[&str; 2]; 2] =
+        [["gnd:1", "Broch, Hermann"], ["gnd:2", "Wien"]];
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            read_table("csv", &options(TableFormat::Csv), &ROWS),
+            "uri,label\ngnd:1,\"Broch, Hermann\"\ngnd:2,Wien\n"
+        );
+
+        let mut options = options(TableFormat::Csv);
+        options.delimiter = Some(';');
+        options.quote_style = QuoteStyle::Always;
+        options.header = false;
+        assert_eq!(
+            read_table("csv-always", &options, &ROWS),
+            "\"gnd:1\";\"Broch, Hermann\"\n\"gnd:2\";\"Wien\"\n"
+        );
+
+        options.delimiter = Some('ß');
+        let writer = Box::new(Vec::<u8>::new());
+        assert!(TableWriter::new(writer, &options).is_err());
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            read_table("tsv", &options(TableFormat::Tsv), &ROWS),
+            "uri\tlabel\ngnd:1\tBroch, Hermann\ngnd:2\tWien\n"
+        );
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let mut options = options(TableFormat::JsonLines);
+        options.header = false;
+        assert_eq!(
+            read_table("jsonl", &options, &ROWS),
+            "{\"uri\":\"gnd:1\",\"label\":\"Broch, Hermann\"}\n\
+             {\"uri\":\"gnd:2\",\"label\":\"Wien\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_arrow() {
+        let rows = vec![["gnd:1", "Wien"]; BATCH_ROWS + 1];
+        let filename =
+            write_table("arrow", &options(TableFormat::Arrow), &rows);
+        let reader =
+            FileReader::try_new(File::open(&filename).unwrap(), None).unwrap();
+
+        let batches = reader.map(Result::unwrap).collect::<Vec<_>>();
+        fs::remove_file(&filename).unwrap();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].num_rows(), BATCH_ROWS);
+        assert_eq!(batches[1].num_rows(), 1);
+        assert_eq!(batches[0].schema().field(1).name(), "label");
+    }
+
+    #[test]
+    fn test_parquet() {
+        let filename =
+            write_table("parquet", &options(TableFormat::Parquet), &ROWS);
+        let content = fs::read(&filename).unwrap();
+        fs::remove_file(&filename).unwrap();
+
+        assert!(content.starts_with(b"PAR1"));
+        assert!(content.ends_with(b"PAR1"));
+    }
+}
@@ -1,14 +1,15 @@
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use clap::{Parser, Subcommand};
-use csv::{Writer, WriterBuilder};
-use gnd::{Concept, Config};
-use pica::matcher::{MatcherFlags, RecordMatcher};
-use pica::ReaderBuilder;
+use gnd::{Collection, Concept, Config, QuoteStyle, SynKind, TableFormat};
+use pica::matcher::RecordMatcher;
+use pica::StringRecord;
 
+use crate::pipeline::Pipeline;
+use crate::table::{TableOptions, TableWriter};
 use crate::{cli_flag, cli_option, CliError, CliResult};
 
 const DEFAULT_FILTER: &str = "002@.0 =~ '^T[bfgpsu][1-7z]$'";
@@ -16,6 +17,11 @@ const DEFAULT_FILTER: &str = "002@.0 =~ '^T[bfgpsu][1-7z]$'";
 #[derive(Subcommand, Debug)]
 pub(crate) enum TabulateCommands {
     Synonyms,
+    Identifiers,
+    Relations,
+    Concepts,
+    Collections,
+    Biographical,
 }
 
 impl FromStr for TabulateCommands {
@@ -24,6 +30,11 @@ impl FromStr for TabulateCommands {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "synonyms" => Ok(TabulateCommands::Synonyms),
+            "identifiers" => Ok(TabulateCommands::Identifiers),
+            "relations" => Ok(TabulateCommands::Relations),
+            "concepts" => Ok(TabulateCommands::Concepts),
+            "collections" => Ok(TabulateCommands::Collections),
+            "biographical" => Ok(TabulateCommands::Biographical),
             _ => Err("invalid tabulate command".to_string()),
         }
     }
@@ -39,43 +50,243 @@ pub(crate) struct TabulateArgs {
     pub(crate) filter: Option<String>,
 
     #[clap(
-        help = "Whether to skip invalid PICA+ records or not.",
+        help = "Whether to skip invalid PICA+ records and concepts or not.",
         long,
         short
     )]
     pub(crate) skip_invalid: bool,
 
+    #[clap(help = "Number of threads used to build the concepts.", long)]
+    pub(crate) threads: Option<usize>,
+
+    #[clap(help = "Output format (csv, tsv, jsonl, arrow or parquet).", long)]
+    pub(crate) format: Option<String>,
+
+    #[clap(help = "Field delimiter of the CSV and TSV output.", long)]
+    pub(crate) delimiter: Option<char>,
+
+    #[clap(
+        help = "When to quote fields (always, necessary, nonnumeric or \
+                never).",
+        long
+    )]
+    pub(crate) quote_style: Option<String>,
+
+    #[clap(help = "Don't write a header row.", long)]
+    pub(crate) no_header: bool,
+
     #[clap(long, short)]
     pub(crate) output: Option<String>,
 
     pub(crate) command: TabulateCommands,
 
-    #[clap(required = false, parse(from_os_str))]
+    #[clap(
+        help = "Input files, directories or glob patterns; plain or gzip \
+                compressed. Reads from stdin if no input or '-' is given.",
+        required = false,
+        parse(from_os_str)
+    )]
     pub(crate) paths: Vec<PathBuf>,
 }
 
 pub(crate) fn tabulate_synonyms(
     concept: &Concept,
-    writer: &mut Writer<Box<dyn Write>>,
-) {
+    config: &Config,
+    writer: &mut TableWriter,
+) -> CliResult<()> {
     for synonym in concept.synset() {
-        writer
-            .write_record(&[
-                concept.uri(),
-                &synonym.kind().to_string(),
-                synonym.label(),
-            ])
-            .unwrap();
+        writer.write_record(&[
+            concept.uri(),
+            &synonym.kind().to_string(),
+            synonym.label(),
+            synonym
+                .language_tag()
+                .unwrap_or(config.skosify.language_tag.as_str()),
+        ])?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn tabulate_identifiers(
+    concept: &Concept,
+    config: &Config,
+    writer: &mut TableWriter,
+) -> CliResult<()> {
+    for identifier in concept.identifiers() {
+        writer.write_record(&[
+            concept.uri(),
+            identifier.system(),
+            identifier.value(),
+            &identifier.uri(config).unwrap_or_default(),
+        ])?;
+    }
+
+    Ok(())
+}
+
+/// Writes the biographical data of a person, one row per date, place and
+/// profession. The target is the URI of the place or profession.
+pub(crate) fn tabulate_biographical(
+    concept: &Concept,
+    writer: &mut TableWriter,
+) -> CliResult<()> {
+    let data = match concept.biographical() {
+        Some(data) => data,
+        None => return Ok(()),
+    };
+
+    let dates = [
+        ("date_of_birth", data.date_of_birth()),
+        ("date_of_death", data.date_of_death()),
+        ("activity_start", data.activity_start()),
+        ("activity_end", data.activity_end()),
+    ];
+
+    for (property, date) in dates {
+        if let Some(date) = date {
+            writer.write_record(&[concept.uri(), property, date, ""])?;
+        }
+    }
+
+    let entities = data
+        .place_of_birth()
+        .map(|place| ("place_of_birth", place))
+        .into_iter()
+        .chain(data.place_of_death().map(|place| ("place_of_death", place)))
+        .chain(data.professions().iter().map(|p| ("profession", p)));
+
+    for (property, entity) in entities {
+        writer.write_record(&[
+            concept.uri(),
+            property,
+            entity.label(),
+            entity.uri().unwrap_or_default(),
+        ])?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn tabulate_relations(
+    concept: &Concept,
+    writer: &mut TableWriter,
+) -> CliResult<()> {
+    for relation in concept.relations() {
+        let target_kind = relation
+            .target_kind()
+            .map(|kind| kind.to_string())
+            .unwrap_or_default();
+
+        writer.write_record(&[
+            concept.uri(),
+            relation.uri(),
+            &relation.kind().to_string(),
+            relation.code(),
+            relation.field(),
+            &target_kind,
+        ])?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn tabulate_concepts(
+    record: &StringRecord,
+    concept: &Concept,
+    collections: &[Collection],
+    writer: &mut TableWriter,
+) -> CliResult<()> {
+    let mut pref_label = "";
+    let mut alt_labels = 0;
+    let mut hidden_labels = 0;
+
+    for synonym in concept.synset() {
+        match *synonym.kind() {
+            SynKind::Preferred => pref_label = synonym.label(),
+            SynKind::Alternative => alt_labels += 1,
+            SynKind::Hidden => hidden_labels += 1,
+        }
     }
+
+    let keys = collections
+        .iter()
+        .flat_map(|collection| collection.keys(record))
+        .collect::<Vec<String>>();
+
+    let (level, entity_codes) = match concept.entity_type() {
+        Some(entity_type) => (
+            entity_type.level().map(String::from).unwrap_or_default(),
+            entity_type.entity_codes().join("|"),
+        ),
+        None => (String::new(), String::new()),
+    };
+
+    writer.write_record(&[
+        concept.uri(),
+        &concept.kind().to_string(),
+        concept.entity_code(),
+        &level,
+        &entity_codes,
+        pref_label,
+        &alt_labels.to_string(),
+        &hidden_labels.to_string(),
+        &concept.relations().len().to_string(),
+        &keys.join("|"),
+    ])?;
+
+    Ok(())
+}
+
+pub(crate) fn tabulate_collections(
+    collections: &[Collection],
+    writer: &mut TableWriter,
+) -> CliResult<()> {
+    for collection in collections {
+        for (uri, members) in collection.collections() {
+            let count = members.len().to_string();
+
+            for member in members {
+                writer.write_record(&[
+                    collection.name(),
+                    uri,
+                    member,
+                    &count,
+                ])?;
+            }
+        }
+    }
+
+    Ok(())
 }
 
 pub(crate) fn run(config: &Config, args: &TabulateArgs) -> CliResult<()> {
+    let mut collections = config
+        .collections
+        .iter()
+        .map(Collection::try_from)
+        .collect::<Result<Vec<Collection>, _>>()?;
+
     let filter_str =
         cli_option!(args.filter, config.concept.filter, DEFAULT_FILTER);
     let skip_invalid =
         cli_flag!(args.skip_invalid, config.concept.skip_invalid);
+    let threads = args.threads.or(config.concept.threads).unwrap_or(1);
+    let options = TableOptions {
+        format: match &args.format {
+            Some(format) => TableFormat::from_str(format)?,
+            None => config.tabulate.format.unwrap_or(TableFormat::Csv),
+        },
+        delimiter: args.delimiter.or(config.tabulate.delimiter),
+        quote_style: match &args.quote_style {
+            Some(style) => QuoteStyle::from_str(style)?,
+            None => {
+                config.tabulate.quote_style.unwrap_or(QuoteStyle::Necessary)
+            }
+        },
+        header: !args.no_header && config.tabulate.header.unwrap_or(true),
+    };
 
-    let matcher_flags = MatcherFlags::default();
     let filter = match RecordMatcher::new(&filter_str) {
         Ok(f) => f,
         Err(_) => {
@@ -87,37 +298,137 @@ pub(crate) fn run(config: &Config, args: &TabulateArgs) -> CliResult<()> {
     };
 
     let writer: Box<dyn Write> = match &args.output {
-        Some(filename) => Box::new(File::create(filename)?),
-        None => Box::new(io::stdout()),
+        Some(filename) => Box::new(BufWriter::new(File::create(filename)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
     };
 
-    let mut writer = WriterBuilder::new().from_writer(writer);
+    let mut writer = TableWriter::new(writer, &options)?;
     match args.command {
         TabulateCommands::Synonyms => {
-            writer.write_record(&["uri", "kind", "synonym"])?;
+            writer.write_header(&["uri", "kind", "synonym", "language"])?;
+        }
+        TabulateCommands::Identifiers => {
+            writer.write_header(&["uri", "system", "identifier", "target"])?;
+        }
+        TabulateCommands::Biographical => {
+            writer.write_header(&["uri", "property", "value", "target"])?;
+        }
+        TabulateCommands::Concepts => {
+            writer.write_header(&[
+                "uri",
+                "kind",
+                "entity_code",
+                "level",
+                "entity_codes",
+                "pref_label",
+                "alt_labels",
+                "hidden_labels",
+                "relations",
+                "collections",
+            ])?;
+        }
+        TabulateCommands::Collections => {
+            writer.write_header(&["name", "uri", "member", "count"])?;
+        }
+        TabulateCommands::Relations => {
+            writer.write_header(&[
+                "source",
+                "target",
+                "kind",
+                "code",
+                "field",
+                "target_kind",
+            ])?;
         }
     }
 
-    for filename in &args.paths {
-        let mut reader = ReaderBuilder::new()
-            .skip_invalid(skip_invalid)
-            .from_path(filename)?;
-
-        for result in reader.records() {
-            let record = result?;
-
-            if filter.is_match(&record, &matcher_flags) {
-                let concept = Concept::from_record(&record, config)?;
-
-                match args.command {
-                    TabulateCommands::Synonyms => {
-                        tabulate_synonyms(&concept, &mut writer)
-                    }
-                }
+    let pipeline = Pipeline::new(config, filter, skip_invalid, threads)?;
+    pipeline.run(&args.paths, |record, concept| match args.command {
+        TabulateCommands::Synonyms => {
+            tabulate_synonyms(&concept, config, &mut writer)
+        }
+        TabulateCommands::Identifiers => {
+            tabulate_identifiers(&concept, config, &mut writer)
+        }
+        TabulateCommands::Relations => {
+            tabulate_relations(&concept, &mut writer)
+        }
+        TabulateCommands::Biographical => {
+            tabulate_biographical(&concept, &mut writer)
+        }
+        TabulateCommands::Concepts => {
+            tabulate_concepts(record, &concept, &collections, &mut writer)
+        }
+        // The members are written after all records were processed.
+        TabulateCommands::Collections => {
+            for collection in collections.iter_mut() {
+                collection.add_record(record, config)
             }
+
+            Ok(())
         }
+    })?;
+
+    if let TabulateCommands::Collections = args.command {
+        tabulate_collections(&collections, &mut writer)?;
     }
 
-    writer.flush()?;
-    Ok(())
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{read_to_string, remove_file};
+    use std::io::Cursor;
+    use std::path::Path;
+
+    use pica::ReaderBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_tabulate_biographical() {
+        let data = "002@ \x1f0Tp1\x1e003@ \x1f0118515551\x1e\
+                    028A \x1fdHermann\x1faBroch\x1e\
+                    041R \x1f9040161226\x1faSchriftsteller\x1f4beru\x1e\
+                    060R \x1fa1886\x1fb1951\x1f4datl\x1e\
+                    065R \x1faWien\x1f4ortg\x1e\n";
+        let record = ReaderBuilder::new()
+            .from_reader(Cursor::new(data.as_bytes().to_vec()), None::<&Path>)
+            .records()
+            .next()
+            .unwrap()
+            .unwrap();
+        let concept =
+            Concept::from_record(&record, &Config::default()).unwrap();
+
+        let filename =
+            std::env::temp_dir().join("gnd-tabulate-biographical.csv");
+        let options = TableOptions {
+            format: TableFormat::Csv,
+            delimiter: None,
+            quote_style: QuoteStyle::Necessary,
+            header: false,
+        };
+        let mut writer = TableWriter::new(
+            Box::new(File::create(&filename).unwrap()),
+            &options,
+        )
+        .unwrap();
+
+        tabulate_biographical(&concept, &mut writer).unwrap();
+        writer.finish().unwrap();
+
+        let content = read_to_string(&filename).unwrap();
+        remove_file(&filename).unwrap();
+
+        assert_eq!(
+            content,
+            "http://d-nb.info/gnd/118515551,date_of_birth,1886,\n\
+             http://d-nb.info/gnd/118515551,date_of_death,1951,\n\
+             http://d-nb.info/gnd/118515551,place_of_birth,Wien,\n\
+             http://d-nb.info/gnd/118515551,profession,Schriftsteller,\
+             http://d-nb.info/gnd/040161226\n"
+        );
+    }
 }
@@ -0,0 +1,240 @@
+use lazy_static::lazy_static;
+use pica::matcher::{FieldMatcher, MatcherFlags};
+use pica::StringRecord;
+use pica_core::Field;
+
+use crate::{Config, GndIdIndex};
+
+lazy_static! {
+    static ref LIFE_DATES: FieldMatcher =
+        FieldMatcher::new("060R.4 == 'datl'").unwrap();
+    static ref ACTIVITY_DATES: FieldMatcher =
+        FieldMatcher::new("060R.4 == 'datw'").unwrap();
+    static ref PLACE_OF_BIRTH: FieldMatcher =
+        FieldMatcher::new("065R.4 == 'ortg'").unwrap();
+    static ref PLACE_OF_DEATH: FieldMatcher =
+        FieldMatcher::new("065R.4 == 'orts'").unwrap();
+}
+
+/// A place or a profession of a person, which refers to another concept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedEntity {
+    pub(crate) idn: Option<String>,
+    pub(crate) uri: Option<String>,
+    pub(crate) label: String,
+}
+
+impl RelatedEntity {
+    /// Builds the entity from a 0xxR field; the label consists of the name
+    /// (`$a`) and the qualifier (`$g`).
+    fn from_field(field: &Field, config: &Config) -> Option<Self> {
+        let mut label = field.first('a')?.to_string();
+        if let Some(qualifier) = field.first('g') {
+            label.push_str(&format!(" ({})", qualifier));
+        }
+
+        let idn = field.first('9').map(|idn| idn.to_string());
+        let uri = idn
+            .as_ref()
+            .map(|idn| config.concept.base_uri.to_owned() + idn);
+
+        Some(Self { idn, uri, label })
+    }
+
+    /// Returns the IDN (`$9`) of the related concept.
+    pub fn idn(&self) -> Option<&str> {
+        self.idn.as_deref()
+    }
+
+    /// Returns the URI of the related concept, if the record links to it.
+    pub fn uri(&self) -> Option<&str> {
+        self.uri.as_deref()
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Biographical data of a person, which is taken from the record the
+/// concept was built from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BiographicalData {
+    pub(crate) date_of_birth: Option<String>,
+    pub(crate) date_of_death: Option<String>,
+    pub(crate) activity_start: Option<String>,
+    pub(crate) activity_end: Option<String>,
+    pub(crate) place_of_birth: Option<RelatedEntity>,
+    pub(crate) place_of_death: Option<RelatedEntity>,
+    pub(crate) professions: Vec<RelatedEntity>,
+}
+
+/// Returns the first field matching the matcher.
+fn find_field<'a>(
+    record: &'a StringRecord,
+    matcher: &FieldMatcher,
+) -> Option<&'a Field> {
+    let flags = MatcherFlags::default();
+    record.iter().find(|field| matcher.is_match(field, &flags))
+}
+
+fn subfield(field: Option<&Field>, code: char) -> Option<String> {
+    field
+        .and_then(|field| field.first(code))
+        .map(|value| value.to_string())
+}
+
+impl BiographicalData {
+    /// Reads the life dates (`060R` with `$4 datl`), the dates of activity
+    /// (`$4 datw`), the places of birth and death (`065R` with `$4 ortg`
+    /// and `orts`) and the professions (`041R` with `$4 berc` or `beru`) of
+    /// a person. Returns `None`, if the record has none of them.
+    pub(crate) fn from_record(
+        record: &StringRecord,
+        config: &Config,
+    ) -> Option<Self> {
+        let life = find_field(record, &LIFE_DATES);
+        let activity = find_field(record, &ACTIVITY_DATES);
+
+        let professions = record
+            .all("041R")
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|field| {
+                let code = subfield(Some(field), '4');
+                matches!(code.as_deref(), Some("berc" | "beru"))
+            })
+            .filter_map(|field| RelatedEntity::from_field(field, config))
+            .collect();
+
+        let data = Self {
+            date_of_birth: subfield(life, 'a'),
+            date_of_death: subfield(life, 'b'),
+            activity_start: subfield(activity, 'a'),
+            activity_end: subfield(activity, 'b'),
+            place_of_birth: find_field(record, &PLACE_OF_BIRTH)
+                .and_then(|field| RelatedEntity::from_field(field, config)),
+            place_of_death: find_field(record, &PLACE_OF_DEATH)
+                .and_then(|field| RelatedEntity::from_field(field, config)),
+            professions,
+        };
+
+        if data == Self::default() {
+            None
+        } else {
+            Some(data)
+        }
+    }
+
+    pub fn date_of_birth(&self) -> Option<&str> {
+        self.date_of_birth.as_deref()
+    }
+
+    pub fn date_of_death(&self) -> Option<&str> {
+        self.date_of_death.as_deref()
+    }
+
+    /// Returns the beginning of the period of activity (`060R` with `$4
+    /// datw`).
+    pub fn activity_start(&self) -> Option<&str> {
+        self.activity_start.as_deref()
+    }
+
+    /// Returns the end of the period of activity.
+    pub fn activity_end(&self) -> Option<&str> {
+        self.activity_end.as_deref()
+    }
+
+    pub fn place_of_birth(&self) -> Option<&RelatedEntity> {
+        self.place_of_birth.as_ref()
+    }
+
+    pub fn place_of_death(&self) -> Option<&RelatedEntity> {
+        self.place_of_death.as_ref()
+    }
+
+    pub fn professions(&self) -> &[RelatedEntity] {
+        &self.professions
+    }
+
+    /// Replaces the IDN-based URIs of the places and professions by URIs
+    /// based on the GND-ID. Entities without a known GND-ID keep their
+    /// label, but lose their URI.
+    pub(crate) fn resolve_gnd_ids(
+        &mut self,
+        index: &GndIdIndex,
+        config: &Config,
+    ) {
+        let entities = self
+            .place_of_birth
+            .iter_mut()
+            .chain(self.place_of_death.iter_mut())
+            .chain(self.professions.iter_mut());
+
+        for entity in entities {
+            entity.uri = entity
+                .idn
+                .as_deref()
+                .and_then(|idn| index.get(idn))
+                .map(|gnd_id| config.concept.base_uri.to_owned() + gnd_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORD: &str = "003@ \x1f0118515551\x1e\
+        041R \x1f9040161226\x1faSchriftsteller\x1f4beru\x1e\
+        041R \x1f9040313166\x1faLyriker\x1f4rela\x1e\
+        060R \x1fa1886\x1fb1951\x1f4datl\x1e\
+        065R \x1f9040658058\x1faWien\x1f4ortg\x1e\
+        065R \x1f9040422704\x1faNew Haven, Conn.\x1f4orts\x1e\n";
+
+    #[test]
+    fn test_biographical_data_from_record() {
+        let config = Config::default();
+        let record = crate::parse_record(RECORD);
+        let data = BiographicalData::from_record(&record, &config).unwrap();
+
+        assert_eq!(data.date_of_birth(), Some("1886"));
+        assert_eq!(data.date_of_death(), Some("1951"));
+        assert_eq!(data.activity_start(), None);
+        assert_eq!(data.place_of_birth().unwrap().label(), "Wien");
+        assert_eq!(
+            data.place_of_death().unwrap().uri(),
+            Some("http://d-nb.info/gnd/040422704")
+        );
+
+        let professions = data.professions();
+        assert_eq!(professions.len(), 1);
+        assert_eq!(professions[0].label(), "Schriftsteller");
+        assert_eq!(professions[0].idn(), Some("040161226"));
+
+        let record = crate::parse_record("003@ \x1f0118515551\x1e\n");
+        assert!(BiographicalData::from_record(&record, &config).is_none());
+    }
+
+    #[test]
+    fn test_resolve_gnd_ids() {
+        let config = Config::default();
+        let record = crate::parse_record(RECORD);
+        let mut data = BiographicalData::from_record(&record, &config).unwrap();
+
+        let mut index = GndIdIndex::new();
+        index.add_record(&crate::parse_record(
+            "003@ \x1f0040658058\x1e\
+             003U \x1fahttp://d-nb.info/gnd/4066009-6\x1e\n",
+        ));
+        data.resolve_gnd_ids(&index, &config);
+
+        assert_eq!(
+            data.place_of_birth().unwrap().uri(),
+            Some("http://d-nb.info/gnd/4066009-6")
+        );
+        assert_eq!(data.place_of_death().unwrap().uri(), None);
+        assert_eq!(data.place_of_death().unwrap().label(), "New Haven, Conn.");
+        assert_eq!(data.professions()[0].uri(), None);
+    }
+}
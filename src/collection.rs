@@ -7,14 +7,16 @@ use regex::Regex;
 use serde::Deserialize;
 
 use crate::concept::ConceptBuilder;
+use crate::entity_type::EntitySelector;
 use crate::subject_term::SubjectTermBuilder;
-use crate::Config;
+use crate::{Config, Error};
 
 #[derive(Debug)]
 pub struct Collection {
     pub(crate) name: String,
     pub(crate) base_uri: String,
     pub(crate) filter: Option<Regex>,
+    pub(crate) select: EntitySelector,
     pub(crate) path: Path,
     pub(crate) items: HashMap<String, Vec<String>>,
     pub(crate) minimum: Option<usize>,
@@ -38,6 +40,7 @@ impl Collection {
             base_uri: base_uri.into(),
             items: HashMap::new(),
             filter,
+            select: EntitySelector::default(),
             path,
             minimum,
             maximum,
@@ -52,6 +55,12 @@ impl Collection {
         &self.base_uri
     }
 
+    /// Returns the selector of the entities, which are members of the
+    /// collection.
+    pub fn select(&self) -> &EntitySelector {
+        &self.select
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -68,8 +77,30 @@ impl Collection {
         &self.maximum
     }
 
-    pub fn add_record(&mut self, record: &StringRecord, config: &Config) {
-        let keys = record
+    /// Returns the collections (ordered by URI), whose number of members
+    /// is within the `minimum` and `maximum` bounds.
+    pub fn collections(&self) -> Vec<(&String, &Vec<String>)> {
+        let mut result = self
+            .items
+            .iter()
+            .filter(|(_, members)| {
+                let length = members.len();
+                self.minimum.map_or(true, |minimum| length >= minimum)
+                    && self.maximum.map_or(true, |maximum| length <= maximum)
+            })
+            .collect::<Vec<_>>();
+
+        result.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        result
+    }
+
+    /// Returns the URIs of all collections the record belongs to.
+    pub fn keys(&self, record: &StringRecord) -> Vec<String> {
+        if !self.select.is_match_record(record) {
+            return vec![];
+        }
+
+        record
             .path(&self.path)
             .iter()
             .map(ToString::to_string)
@@ -81,7 +112,11 @@ impl Collection {
                 }
             })
             .map(|f| String::from(&self.base_uri) + &f)
-            .collect::<Vec<String>>();
+            .collect::<Vec<String>>()
+    }
+
+    pub fn add_record(&mut self, record: &StringRecord, config: &Config) {
+        let keys = self.keys(record);
 
         if !keys.is_empty() {
             if let Ok(uri) = SubjectTermBuilder::uri(record, config) {
@@ -106,35 +141,49 @@ pub struct CollectionSpec {
     pub base_uri: String,
     pub path: String,
     pub filter: Option<String>,
+    #[serde(default)]
+    pub select: EntitySelector,
     pub minimum: Option<usize>,
     pub maximum: Option<usize>,
 }
 
 impl TryFrom<&CollectionSpec> for Collection {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(spec: &CollectionSpec) -> Result<Self, Self::Error> {
         let path = match Path::from_str(&spec.path) {
             Ok(path) => path,
-            Err(_) => return Err("Invalid path".to_string()),
+            Err(_) => {
+                return Err(Error::InvalidPath {
+                    path: spec.path.to_owned(),
+                })
+            }
         };
 
         let filter = if let Some(filter) = &spec.filter {
             match Regex::new(filter) {
                 Ok(re) => Some(re),
-                _ => return Err("Invalid Regex".to_string()),
+                Err(e) => {
+                    return Err(Error::InvalidRegex {
+                        pattern: filter.to_owned(),
+                        source: e,
+                    })
+                }
             }
         } else {
             None
         };
 
-        Ok(Collection::new(
-            &spec.name,
-            &spec.base_uri,
-            filter,
-            path,
-            spec.minimum,
-            spec.maximum,
-        ))
+        Ok(Collection {
+            select: spec.select.clone(),
+            ..Collection::new(
+                &spec.name,
+                &spec.base_uri,
+                filter,
+                path,
+                spec.minimum,
+                spec.maximum,
+            )
+        })
     }
 }
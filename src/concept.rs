@@ -1,22 +1,30 @@
+use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
 
 use lazy_static::lazy_static;
 use pica::{Path, StringRecord};
+use pica_core::Field;
+use regex::Regex;
+use serde::Deserialize;
 
 lazy_static! {
     static ref IDN_PATH: Path = Path::from_str("003@.0").unwrap();
     static ref GND_ID_PATH: Path = Path::from_str("003U.a").unwrap();
-    static ref BBG_PATH: Path = Path::from_str("002@.0").unwrap();
 }
 
 use crate::conference::ConferenceBuilder;
+use crate::config::UriSource;
 use crate::corporate_body::CorporateBodyBuilder;
 use crate::person::PersonBuilder;
 use crate::place::PlaceBuilder;
 use crate::subject_term::SubjectTermBuilder;
+use crate::synset::SynonymBuilder;
 use crate::work::WorkBuilder;
-use crate::{Config, Error, Relation, Result, SynSet, Synonym};
+use crate::{
+    BiographicalData, Config, EntityType, Error, GndIdIndex, Identifier,
+    Relation, RelationKind, Result, SynKind, SynSet, Synonym,
+};
 
 #[derive(Debug)]
 pub struct Concept {
@@ -24,6 +32,10 @@ pub struct Concept {
     pub(crate) kind: ConceptKind,
     pub(crate) synset: SynSet,
     pub(crate) relations: Vec<Relation>,
+    pub(crate) identifiers: Vec<Identifier>,
+    pub(crate) entity_type: Option<EntityType>,
+    pub(crate) superior: Option<String>,
+    pub(crate) biographical: Option<BiographicalData>,
 }
 
 impl Concept {
@@ -36,6 +48,10 @@ impl Concept {
             synset: SynSet::new(),
             kind,
             relations,
+            identifiers: vec![],
+            entity_type: None,
+            superior: None,
+            biographical: None,
         }
     }
 
@@ -43,6 +59,17 @@ impl Concept {
         self.synset.insert(synonym)
     }
 
+    /// Adds the synonym, unless the concept has a synonym with the same
+    /// label already (regardless of its kind).
+    pub fn add_synonym_if_new(&mut self, synonym: Synonym) -> bool {
+        let exists = self
+            .synset
+            .iter()
+            .any(|other| other.label() == synonym.label());
+
+        !exists && self.synset.insert(synonym)
+    }
+
     pub fn uri(&self) -> &str {
         &self.uri
     }
@@ -58,9 +85,130 @@ impl Concept {
     pub fn relations(&self) -> &Vec<Relation> {
         &self.relations
     }
+
+    /// Returns the entity code (`002@.0`) of the record the concept was
+    /// built from.
+    pub fn entity_code(&self) -> &str {
+        self.entity_type
+            .as_ref()
+            .map(EntityType::code)
+            .unwrap_or_default()
+    }
+
+    /// Returns the entity type (record type, level of cataloguing and
+    /// entity codes) of the record the concept was built from.
+    pub fn entity_type(&self) -> Option<&EntityType> {
+        self.entity_type.as_ref()
+    }
+
+    /// Returns the heading of the superior corporate body, which is given
+    /// by the subordinate units (`$b`) of the heading.
+    pub fn superior(&self) -> Option<&str> {
+        self.superior.as_deref()
+    }
+
+    /// Returns the biographical data of a person.
+    pub fn biographical(&self) -> Option<&BiographicalData> {
+        self.biographical.as_ref()
+    }
+
+    /// Adds a hierarchical relation (`adue`) to the superior corporate
+    /// body, unless the concept is already related to it or to another
+    /// superior corporate body.
+    pub fn add_superior(&mut self, uri: &str) {
+        let exists = self
+            .relations
+            .iter()
+            .any(|relation| relation.code == "adue" || relation.uri == uri);
+
+        if !exists && uri != self.uri {
+            self.relations.push(Relation {
+                field: "029R".to_string(),
+                ..Relation::new(uri, RelationKind::Broader, "adue")
+            });
+        }
+    }
+
+    /// Returns the identifiers of the concept in external systems.
+    pub fn identifiers(&self) -> &Vec<Identifier> {
+        &self.identifiers
+    }
+
+    /// Replaces the IDN-based URIs of the relation targets by URIs based on
+    /// the GND-ID. Relations, whose target has no known GND-ID, are
+    /// removed from the concept and returned.
+    pub fn resolve_gnd_ids(
+        &mut self,
+        index: &GndIdIndex,
+        config: &Config,
+    ) -> Vec<Relation> {
+        let (resolved, unresolved) = self
+            .relations
+            .drain(..)
+            .partition::<Vec<_>, _>(|r| index.get(r.idn()).is_some());
+
+        self.relations = resolved
+            .into_iter()
+            .map(|mut r| {
+                r.uri = config.concept.base_uri.to_owned()
+                    + index.get(r.idn()).unwrap();
+                r
+            })
+            .collect();
+
+        if let Some(biographical) = self.biographical.as_mut() {
+            biographical.resolve_gnd_ids(index, config);
+        }
+
+        unresolved
+    }
+
+    /// Appends the identifier of the concept (the last segment of its URI)
+    /// to the preferred label in order to distinguish it from the preferred
+    /// labels of other concepts. The former preferred label is kept as
+    /// hidden label.
+    pub fn disambiguate_pref_label(&mut self) {
+        let pref_label = match self
+            .synset
+            .iter()
+            .find(|synonym| *synonym.kind() == SynKind::Preferred)
+        {
+            Some(synonym) => SynonymBuilder::from(synonym),
+            None => return,
+        };
+
+        let id = self.uri.rsplit('/').next().unwrap_or_default().to_owned();
+        let disambiguated =
+            pref_label.clone().push_str(format!(" [{}]", id)).build();
+        let hidden = pref_label.kind(SynKind::Hidden).build();
+
+        if let (Some(disambiguated), Some(hidden)) = (disambiguated, hidden) {
+            self.synset
+                .retain(|synonym| *synonym.kind() != SynKind::Preferred);
+            self.synset.insert(disambiguated);
+            self.synset.insert(hidden);
+        }
+    }
+
+    /// Retains only the synonyms specified by the predicate.
+    pub fn retain_synonyms<F>(&mut self, f: F)
+    where
+        F: FnMut(&Synonym) -> bool,
+    {
+        self.synset.retain(f)
+    }
+
+    /// Retains only the relations specified by the predicate.
+    pub fn retain_relations<F>(&mut self, f: F)
+    where
+        F: FnMut(&Relation) -> bool,
+    {
+        self.relations.retain(f)
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ConceptKind {
     Person,
     CorporateBody,
@@ -83,19 +231,64 @@ impl fmt::Display for ConceptKind {
     }
 }
 
+/// Returns the GND-ID of the record, which is the last segment of the URI
+/// in `003U.a`.
+pub(crate) fn gnd_id(record: &StringRecord) -> Option<String> {
+    record
+        .path(&GND_ID_PATH)
+        .first()
+        .map(ToString::to_string)
+        .and_then(|uri| uri.rsplit('/').next().map(ToString::to_string))
+        .filter(|gnd_id| !gnd_id.is_empty())
+}
+
+/// Returns the IDN of the record or an empty string, if the record has no
+/// IDN.
+pub(crate) fn idn(record: &StringRecord) -> String {
+    record
+        .path(&IDN_PATH)
+        .first()
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
+
 pub(crate) trait ConceptBuilder {
     fn from_record(record: &StringRecord, config: &Config) -> Result<Concept>;
 
+    /// Returns the first field with the given tag or an error, if the record
+    /// doesn't contain such a field.
+    fn required_field<'a>(
+        record: &'a StringRecord,
+        tag: &str,
+    ) -> Result<&'a Field> {
+        record.first(tag).ok_or_else(|| Error::MissingField {
+            idn: idn(record),
+            tag: tag.to_string(),
+        })
+    }
+
     fn uri(record: &StringRecord, config: &Config) -> Result<String> {
-        let idn = record
-            .path(&IDN_PATH)
-            .first()
-            .map(ToString::to_string)
-            .ok_or_else(|| {
-                Error::Concept("could not find valid idn".to_string())
-            })?;
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^[0-9]+[0-9X]$").unwrap();
+        }
+
+        let idn = idn(record);
+        if !RE.is_match(&idn) {
+            return Err(Error::InvalidIdn { idn });
+        }
 
-        Ok(config.concept.base_uri.to_owned() + &idn)
+        match config.concept.uri_source {
+            Some(UriSource::GndId) => match gnd_id(record) {
+                Some(gnd_id) => {
+                    Ok(config.concept.base_uri.to_owned() + &gnd_id)
+                }
+                None => Err(Error::MissingField {
+                    idn,
+                    tag: "003U".to_string(),
+                }),
+            },
+            _ => Ok(config.concept.base_uri.to_owned() + &idn),
+        }
     }
 
     fn relations(record: &StringRecord, config: &Config) -> Vec<Relation> {
@@ -103,6 +296,7 @@ pub(crate) trait ConceptBuilder {
             .iter()
             .flat_map(|f| record.all(f).unwrap_or_default())
             .filter_map(|f| Relation::try_from(f).ok())
+            .filter(|r| !config.relation.drop.contains(&r.code))
             .map(|mut r| {
                 r.uri = config.concept.base_uri.to_owned() + &r.uri;
                 r
@@ -118,17 +312,65 @@ impl Concept {
         record: &StringRecord,
         config: &Config,
     ) -> Result<Concept> {
-        let bbg = record.path(&BBG_PATH).first().unwrap().to_string();
-
-        match &bbg[1..2] {
-            "p" => PersonBuilder::from_record(record, config),
-            "b" => CorporateBodyBuilder::from_record(record, config),
-            "f" => ConferenceBuilder::from_record(record, config),
-            "g" => PlaceBuilder::from_record(record, config),
-            "s" => SubjectTermBuilder::from_record(record, config),
-            "u" => WorkBuilder::from_record(record, config),
-            s => Err(Error::Concept(format!("unknown concept kind '{}'", s))),
+        let entity_type = EntityType::from_record(record)?;
+
+        let mut concept = match entity_type.kind() {
+            ConceptKind::Person => PersonBuilder::from_record(record, config),
+            ConceptKind::CorporateBody => {
+                CorporateBodyBuilder::from_record(record, config)
+            }
+            ConceptKind::Conference => {
+                ConferenceBuilder::from_record(record, config)
+            }
+            ConceptKind::Place => PlaceBuilder::from_record(record, config),
+            ConceptKind::SubjectTerm => {
+                SubjectTermBuilder::from_record(record, config)
+            }
+            ConceptKind::Work => WorkBuilder::from_record(record, config),
+        }?;
+
+        concept.identifiers = Identifier::from_record(record);
+        concept.entity_type = Some(entity_type);
+
+        // Derived synonyms are only added, if there is no synonym with the
+        // same label yet.
+        let mut labels = concept
+            .synset()
+            .iter()
+            .map(|synonym| synonym.label().to_owned())
+            .collect::<HashSet<String>>();
+
+        let derived = config
+            .synonym_rules
+            .iter()
+            .filter(|rule| rule.applies_to(concept.kind()))
+            .flat_map(|rule| {
+                concept
+                    .synset()
+                    .iter()
+                    .filter_map(|synonym| rule.apply(synonym, config))
+            })
+            .filter(|synonym| labels.insert(synonym.label().to_owned()))
+            .collect::<Vec<Synonym>>();
+
+        for synonym in derived {
+            concept.add_synonym(synonym);
+        }
+
+        if !config.concept.translit_variants.is_empty() {
+            let hidden = concept
+                .synset()
+                .iter()
+                .flat_map(|synonym| synonym.transliterations(config))
+                .filter(|synonym| labels.insert(synonym.label().to_owned()))
+                .collect::<Vec<Synonym>>();
+
+            for synonym in hidden {
+                concept.add_synonym(synonym);
+            }
         }
+
+        Ok(concept)
     }
 }
 
@@ -145,4 +387,52 @@ mod tests {
         assert_eq!(ConceptKind::SubjectTerm.to_string(), "Subject term");
         assert_eq!(ConceptKind::Work.to_string(), "Work");
     }
+
+    #[test]
+    fn test_from_malformed_record() {
+        let config = Config::default();
+
+        let record =
+            crate::parse_record("002@ \x1f0Tp1\x1e003@ \x1f0118515551\x1e\n");
+        match Concept::from_record(&record, &config) {
+            Err(Error::MissingField { idn, tag }) => {
+                assert_eq!(idn, "118515551");
+                assert_eq!(tag, "028A");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let record =
+            crate::parse_record("002@ \x1f0T\x1e003@ \x1f0118515551\x1e\n");
+        match Concept::from_record(&record, &config) {
+            Err(Error::UnknownEntityKind { idn, code }) => {
+                assert_eq!(idn, "118515551");
+                assert_eq!(code, "T");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let record = crate::parse_record(
+            "002@ \x1f0Tp1\x1e003@ \x1f0abc\x1e\
+             028A \x1fdHermann\x1faBroch\x1e\n",
+        );
+        match Concept::from_record(&record, &config) {
+            Err(Error::InvalidIdn { idn }) => assert_eq!(idn, "abc"),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_add_synonym_if_new() {
+        let mut concept = Concept::new("123", vec![], ConceptKind::Place);
+        assert!(concept
+            .add_synonym_if_new(Synonym::new("Berlin", SynKind::Preferred)));
+        assert!(!concept
+            .add_synonym_if_new(Synonym::new("Berlin", SynKind::Hidden)));
+        assert!(concept.add_synonym_if_new(Synonym::new(
+            "Berlin (West)",
+            SynKind::Hidden
+        )));
+        assert_eq!(concept.synset().len(), 2);
+    }
 }
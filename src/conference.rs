@@ -2,9 +2,10 @@ use std::fmt::Write as _;
 
 use crate::concept::ConceptBuilder;
 use crate::config::TranslitChoice;
-use crate::{Concept, ConceptKind, Config, Result, SynKind, Synonym};
+use crate::{language, Concept, ConceptKind, Config, Result, SynKind, Synonym};
 use pica::StringRecord;
 use pica_core::Field;
+use regex::Regex;
 
 const CHECK: [char; 4] = ['n', 'd', 'c', 'g'];
 
@@ -15,16 +16,18 @@ pub(crate) fn get_synonym(
     kind: SynKind,
     translit: Option<&TranslitChoice>,
     min_length: usize,
-    synonym_filter: Option<&String>,
+    synonym_filter: Option<&Regex>,
 ) -> Option<Synonym> {
     let mut synonym = Synonym::builder(kind)
         .translit(translit)
         .min_length(min_length)
-        .filter(synonym_filter);
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field));
     let mut parens = String::new();
 
     for subfield in field.iter() {
-        let value = String::from_utf8(subfield.value().to_vec()).unwrap();
+        let value = subfield.value().to_string();
 
         if !CHECK.contains(&subfield.code()) && !parens.is_empty() {
             synonym = synonym.push_str(&format!(" ({})", parens));
@@ -65,7 +68,7 @@ pub(crate) fn get_synonym(
 impl ConceptBuilder for ConferenceBuilder {
     fn from_record(record: &StringRecord, config: &Config) -> Result<Concept> {
         let min_length = config.concept.min_synonym_length.unwrap_or_default();
-        let synonym_filter = config.concept.synonym_filter.as_ref();
+        let synonym_filter = config.concept.synonym_filter.as_deref();
         let translit = config.concept.translit.as_ref();
 
         let mut concept = Concept::new(
@@ -75,7 +78,7 @@ impl ConceptBuilder for ConferenceBuilder {
         );
 
         if let Some(synonym) = get_synonym(
-            record.first("030A").unwrap(),
+            Self::required_field(record, "030A")?,
             SynKind::Preferred,
             translit,
             min_length,
@@ -1,9 +1,14 @@
+use std::collections::HashMap;
 use std::fs::read_to_string;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use serde::Deserialize;
 
 use crate::collection::CollectionSpec;
+use crate::entity_type::EntitySelector;
+use crate::relation::is_valid_predicate;
+use crate::synonym_rule::{Pattern, SynonymRule};
 use crate::{Error, Result};
 
 #[derive(Deserialize, Default, PartialEq, Eq, Debug)]
@@ -11,37 +16,140 @@ pub struct Config {
     pub concept: ConceptConfig,
     #[serde(rename = "collection", default = "Vec::new")]
     pub collections: Vec<CollectionSpec>,
+    #[serde(rename = "synonym_rule", default = "Vec::new")]
+    pub synonym_rules: Vec<SynonymRule>,
     pub skosify: SkosifyConfig,
+    #[serde(default)]
+    pub relation: RelationConfig,
+    #[serde(default)]
+    pub identifier: IdentifierConfig,
+    #[serde(default)]
+    pub tabulate: TabulateConfig,
 }
 
 #[derive(Deserialize, PartialEq, Eq, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ConceptConfig {
     pub filter: Option<String>,
+    #[serde(default)]
+    pub select: EntitySelector,
     pub base_uri: String,
     pub skip_invalid: bool,
     pub translit: Option<TranslitChoice>,
+    #[serde(default)]
+    pub translit_variants: Vec<TranslitVariant>,
     pub min_synonym_length: Option<usize>,
-    pub synonym_filter: Option<String>,
+    pub synonym_filter: Option<Pattern>,
     pub person_no_initials: Option<bool>,
     pub person_no_modern_names: Option<bool>,
+    pub person_label_fallback: Option<bool>,
+    pub unique_pref_labels: Option<bool>,
+    pub pref_label_report: Option<String>,
+    pub corporate_body_unit_labels: Option<bool>,
+    pub corporate_body_hierarchy: Option<bool>,
+    pub strip_qualifiers: Option<bool>,
+    pub ambiguous_labels: Option<AmbiguousLabels>,
+    pub ambiguous_report: Option<String>,
+    pub threads: Option<usize>,
+    pub uri_source: Option<UriSource>,
 }
 
 impl Default for ConceptConfig {
     fn default() -> Self {
         Self {
             filter: None,
+            select: EntitySelector::default(),
             base_uri: "http://d-nb.info/gnd/".to_string(),
             skip_invalid: false,
             translit: None,
+            translit_variants: vec![],
             min_synonym_length: None,
             synonym_filter: None,
             person_no_initials: None,
             person_no_modern_names: None,
+            person_label_fallback: None,
+            unique_pref_labels: None,
+            pref_label_report: None,
+            corporate_body_unit_labels: None,
+            corporate_body_hierarchy: None,
+            strip_qualifiers: None,
+            ambiguous_labels: None,
+            ambiguous_report: None,
+            threads: None,
+            uri_source: None,
         }
     }
 }
 
+/// How to handle headings without qualifiers, which are shared by several
+/// concepts.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum AmbiguousLabels {
+    Keep,
+    Suppress,
+    Report,
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IdentifierConfig {
+    /// Maps the (lowercase) name of an external system to an URI template.
+    /// The placeholder `{}` is replaced by the identifier.
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
+    pub predicate: Option<LinkPredicate>,
+}
+
+/// The predicate used to link a concept to an external identifier.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkPredicate {
+    ExactMatch,
+    SameAs,
+}
+
+/// The identifier, which is appended to the `base_uri` in order to build
+/// the URI of a concept.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+pub enum UriSource {
+    Idn,
+    GndId,
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelationConfig {
+    /// Maps relation codes (`$4`) to predicates. A predicate is either a
+    /// full URI or a name prefixed by `skos:` or `gndo:`.
+    #[serde(default)]
+    pub mapping: HashMap<String, String>,
+    /// Relation codes, which are dropped entirely.
+    #[serde(default)]
+    pub drop: Vec<String>,
+}
+
+impl RelationConfig {
+    /// Checks that each predicate of the mapping is an absolute IRI (after
+    /// expanding the `skos:` and `gndo:` prefixes).
+    pub fn validate(&self) -> Result<()> {
+        for predicate in self.mapping.values() {
+            if !is_valid_predicate(predicate) {
+                return Err(Error::InvalidValue {
+                    option: "relation.mapping".to_string(),
+                    value: predicate.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "lowercase")]
@@ -52,12 +160,180 @@ pub enum TranslitChoice {
     Nfkd,
 }
 
+/// A transliteration of a label, which is added as hidden label.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslitVariant {
+    /// Removes diacritics and folds Latin letters to ASCII.
+    Ascii,
+    /// Expands the German umlauts (ä → ae, ö → oe, ü → ue, ß → ss).
+    Umlaut,
+    /// Romanizes Cyrillic and Greek letters.
+    Romanize,
+}
+
 #[derive(Deserialize, PartialEq, Eq, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct SkosifyConfig {
     pub pretty: bool,
     #[serde(default = "default_language_tag")]
     pub language_tag: String,
+    #[serde(default)]
+    pub streaming: bool,
+    #[serde(default)]
+    pub skosxl: bool,
+    #[serde(default)]
+    pub inverse_relations: bool,
+    pub dangling_relations: Option<DanglingRelations>,
+    pub dangling_report: Option<String>,
+    pub format: Option<RdfFormat>,
+    pub profile: Option<OutputProfile>,
+}
+
+/// How to handle relations, whose target concept doesn't occur in the
+/// input or doesn't match the concept filter.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum DanglingRelations {
+    Keep,
+    Drop,
+    Report,
+}
+
+impl FromStr for DanglingRelations {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "keep" => Ok(DanglingRelations::Keep),
+            "drop" => Ok(DanglingRelations::Drop),
+            "report" => Ok(DanglingRelations::Report),
+            _ => Err(Error::InvalidValue {
+                option: "dangling_relations".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum RdfFormat {
+    NTriples,
+    Turtle,
+    RdfXml,
+    JsonLd,
+}
+
+impl FromStr for RdfFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ntriples" | "nt" => Ok(RdfFormat::NTriples),
+            "turtle" | "ttl" => Ok(RdfFormat::Turtle),
+            "rdfxml" | "xml" => Ok(RdfFormat::RdfXml),
+            "jsonld" => Ok(RdfFormat::JsonLd),
+            _ => Err(Error::InvalidValue {
+                option: "format".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// The vocabulary, which is used to describe the concepts: SKOS or the
+/// classes and properties of the GND Ontology.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputProfile {
+    Skos,
+    Gndo,
+}
+
+impl FromStr for OutputProfile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "skos" => Ok(OutputProfile::Skos),
+            "gndo" => Ok(OutputProfile::Gndo),
+            _ => Err(Error::InvalidValue {
+                option: "profile".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TabulateConfig {
+    pub format: Option<TableFormat>,
+    pub delimiter: Option<char>,
+    pub quote_style: Option<QuoteStyle>,
+    pub header: Option<bool>,
+}
+
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+    JsonLines,
+    Arrow,
+    Parquet,
+}
+
+impl FromStr for TableFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "csv" => Ok(TableFormat::Csv),
+            "tsv" => Ok(TableFormat::Tsv),
+            "jsonlines" | "jsonl" => Ok(TableFormat::JsonLines),
+            "arrow" => Ok(TableFormat::Arrow),
+            "parquet" => Ok(TableFormat::Parquet),
+            _ => Err(Error::InvalidValue {
+                option: "format".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// When to quote the fields of a CSV/TSV table.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    Always,
+    Necessary,
+    NonNumeric,
+    Never,
+}
+
+impl FromStr for QuoteStyle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "always" => Ok(QuoteStyle::Always),
+            "necessary" => Ok(QuoteStyle::Necessary),
+            "nonnumeric" => Ok(QuoteStyle::NonNumeric),
+            "never" => Ok(QuoteStyle::Never),
+            _ => Err(Error::InvalidValue {
+                option: "quote_style".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
 }
 
 fn default_language_tag() -> String {
@@ -69,6 +345,13 @@ impl Default for SkosifyConfig {
         Self {
             pretty: true,
             language_tag: default_language_tag(),
+            streaming: false,
+            skosxl: false,
+            inverse_relations: false,
+            dangling_relations: None,
+            dangling_report: None,
+            format: None,
+            profile: None,
         }
     }
 }
@@ -77,16 +360,22 @@ impl Config {
     pub fn from_file(filename: &PathBuf) -> Result<Config> {
         let content = match read_to_string(filename) {
             Ok(content) => content,
-            _ => {
-                return Err(Error::Config(
-                    "unable to open config file '{}'".to_string(),
-                ))
+            Err(e) => {
+                return Err(Error::ConfigIo {
+                    path: filename.to_owned(),
+                    source: e,
+                })
             }
         };
 
         let config = match toml::from_str(&content) {
             Ok(config) => config,
-            Err(e) => return Err(Error::Config(e.to_string())),
+            Err(e) => {
+                return Err(Error::ConfigParse {
+                    path: filename.to_owned(),
+                    source: e,
+                })
+            }
         };
 
         Ok(config)
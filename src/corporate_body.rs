@@ -1,24 +1,19 @@
 use crate::concept::ConceptBuilder;
 use crate::config::TranslitChoice;
-use crate::{Concept, ConceptKind, Config, Result, SynKind, Synonym};
+use crate::synset::SynonymBuilder;
+use crate::{language, Concept, ConceptKind, Config, Result, SynKind, Synonym};
 use pica::StringRecord;
 use pica_core::Field;
+use regex::Regex;
 
 pub(crate) struct CorporateBodyBuilder;
 
-pub(crate) fn get_synonym(
+fn push_subfields(
+    mut synonym: SynonymBuilder,
     field: &Field,
-    kind: SynKind,
-    translit: Option<&TranslitChoice>,
-    min_length: usize,
-    synonym_filter: Option<&String>,
-) -> Option<Synonym> {
-    let mut synonym = Synonym::builder(kind)
-        .translit(translit)
-        .min_length(min_length)
-        .filter(synonym_filter);
-
-    for subfield in field.iter() {
+    limit: usize,
+) -> SynonymBuilder {
+    for subfield in field.iter().take(limit) {
         let value = subfield.value().to_string();
 
         match subfield.code() {
@@ -38,14 +33,77 @@ pub(crate) fn get_synonym(
         }
     }
 
-    synonym.build()
+    synonym
+}
+
+pub(crate) fn get_synonym(
+    field: &Field,
+    kind: SynKind,
+    translit: Option<&TranslitChoice>,
+    min_length: usize,
+    synonym_filter: Option<&Regex>,
+) -> Option<Synonym> {
+    let synonym = Synonym::builder(kind)
+        .translit(translit)
+        .min_length(min_length)
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field));
+
+    push_subfields(synonym, field, usize::MAX).build()
+}
+
+/// Returns the name of the subordinate unit (the last `$b`) as hidden
+/// label.
+fn get_unit_synonym(
+    field: &Field,
+    translit: Option<&TranslitChoice>,
+    min_length: usize,
+    synonym_filter: Option<&Regex>,
+) -> Option<Synonym> {
+    let unit = field
+        .iter()
+        .filter(|subfield| subfield.code() == 'b')
+        .last()?;
+
+    Synonym::builder(SynKind::Hidden)
+        .translit(translit)
+        .min_length(min_length)
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field))
+        .push_str(unit.value().to_string())
+        .build()
+}
+
+/// Returns the heading of the superior corporate body, which is the
+/// heading without the last subordinate unit (`$b`).
+fn get_superior(
+    field: &Field,
+    translit: Option<&TranslitChoice>,
+) -> Option<String> {
+    let position = field
+        .iter()
+        .enumerate()
+        .filter(|(_, subfield)| subfield.code() == 'b')
+        .map(|(i, _)| i)
+        .last()?;
+
+    let synonym = SynonymBuilder::new(SynKind::Preferred).translit(translit);
+    push_subfields(synonym, field, position)
+        .build()
+        .map(|synonym| synonym.label().to_owned())
 }
 
 impl ConceptBuilder for CorporateBodyBuilder {
     fn from_record(record: &StringRecord, config: &Config) -> Result<Concept> {
         let min_length = config.concept.min_synonym_length.unwrap_or_default();
-        let synonm_filter = config.concept.synonym_filter.as_ref();
+        let synonm_filter = config.concept.synonym_filter.as_deref();
         let translit = config.concept.translit.as_ref();
+        let unit_labels = config
+            .concept
+            .corporate_body_unit_labels
+            .unwrap_or_default();
 
         let mut concept = Concept::new(
             Self::uri(record, config)?,
@@ -53,8 +111,9 @@ impl ConceptBuilder for CorporateBodyBuilder {
             ConceptKind::CorporateBody,
         );
 
+        let field = Self::required_field(record, "029A")?;
         if let Some(synonym) = get_synonym(
-            record.first("029A").unwrap(),
+            field,
             SynKind::Preferred,
             translit,
             min_length,
@@ -63,6 +122,12 @@ impl ConceptBuilder for CorporateBodyBuilder {
             concept.add_synonym(synonym);
         }
 
+        if config.concept.corporate_body_hierarchy.unwrap_or_default() {
+            concept.superior = get_superior(field, translit);
+        }
+
+        let mut fields = vec![field];
+
         for field in record.all("029@").unwrap_or_default() {
             if let Some(synonym) = get_synonym(
                 field,
@@ -73,8 +138,52 @@ impl ConceptBuilder for CorporateBodyBuilder {
             ) {
                 concept.add_synonym(synonym);
             }
+
+            fields.push(field);
+        }
+
+        if unit_labels {
+            for field in fields {
+                if let Some(synonym) =
+                    get_unit_synonym(field, translit, min_length, synonm_filter)
+                {
+                    concept.add_synonym_if_new(synonym);
+                }
+            }
         }
 
         Ok(concept)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_superior_and_unit_synonym() {
+        let record = crate::parse_record(
+            "003@ \x1f0123\x1e029A \x1faBayern\x1fbStaatsministerium für \
+             Unterricht und Kultus\x1fbAbteilung II\x1e\n",
+        );
+        let field = record.first("029A").unwrap();
+
+        assert_eq!(
+            get_superior(field, None),
+            Some(
+                "Bayern / Staatsministerium für Unterricht und Kultus"
+                    .to_string()
+            )
+        );
+
+        let unit = get_unit_synonym(field, None, 0, None).unwrap();
+        assert_eq!(unit.label(), "Abteilung II");
+        assert_eq!(unit.kind(), &SynKind::Hidden);
+
+        let record =
+            crate::parse_record("003@ \x1f0123\x1e029A \x1faBayern\x1e\n");
+        let field = record.first("029A").unwrap();
+        assert_eq!(get_superior(field, None), None);
+        assert!(get_unit_synonym(field, None, 0, None).is_none());
+    }
+}
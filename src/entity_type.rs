@@ -0,0 +1,162 @@
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use pica::{Path, StringRecord};
+use serde::Deserialize;
+
+use crate::concept::idn;
+use crate::{ConceptKind, Error, Result};
+
+lazy_static! {
+    static ref BBG_PATH: Path = Path::from_str("002@.0").unwrap();
+    static ref ENTITY_CODE_PATH: Path = Path::from_str("004B.a").unwrap();
+}
+
+/// The type of an entity, which is given by the record type (`002@.0`,
+/// e.g. "Tp1") and the entity codes (`004B.a`, e.g. "piz").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityType {
+    code: String,
+    kind: ConceptKind,
+    level: Option<char>,
+    entity_codes: Vec<String>,
+}
+
+impl EntityType {
+    pub fn from_record(record: &StringRecord) -> Result<EntityType> {
+        let code = record
+            .path(&BBG_PATH)
+            .first()
+            .map(ToString::to_string)
+            .ok_or_else(|| Error::MissingField {
+                idn: idn(record),
+                tag: "002@".to_string(),
+            })?;
+
+        let kind = match code.get(1..2).unwrap_or_default() {
+            "p" => ConceptKind::Person,
+            "b" => ConceptKind::CorporateBody,
+            "f" => ConceptKind::Conference,
+            "g" => ConceptKind::Place,
+            "s" => ConceptKind::SubjectTerm,
+            "u" => ConceptKind::Work,
+            _ => {
+                return Err(Error::UnknownEntityKind {
+                    idn: idn(record),
+                    code,
+                })
+            }
+        };
+
+        let entity_codes = record
+            .path(&ENTITY_CODE_PATH)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        Ok(EntityType {
+            level: code.chars().nth(2),
+            code,
+            kind,
+            entity_codes,
+        })
+    }
+
+    /// Returns the record type (`002@.0`).
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn kind(&self) -> &ConceptKind {
+        &self.kind
+    }
+
+    /// Returns the level of cataloguing (`1`–`7` or `z`).
+    pub fn level(&self) -> Option<char> {
+        self.level
+    }
+
+    /// Returns the entity codes (`004B.a`).
+    pub fn entity_codes(&self) -> &[String] {
+        &self.entity_codes
+    }
+}
+
+/// Selects entities by their kind, level of cataloguing and entity codes.
+/// Empty lists select all entities.
+#[derive(Deserialize, Default, PartialEq, Eq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EntitySelector {
+    #[serde(default)]
+    pub kinds: Vec<ConceptKind>,
+    #[serde(default)]
+    pub levels: Vec<char>,
+    #[serde(default)]
+    pub entity_codes: Vec<String>,
+}
+
+impl EntitySelector {
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+            && self.levels.is_empty()
+            && self.entity_codes.is_empty()
+    }
+
+    pub fn is_match(&self, entity_type: &EntityType) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(entity_type.kind()))
+            && (self.levels.is_empty()
+                || entity_type
+                    .level()
+                    .map(|level| self.levels.contains(&level))
+                    .unwrap_or_default())
+            && (self.entity_codes.is_empty()
+                || entity_type
+                    .entity_codes()
+                    .iter()
+                    .any(|code| self.entity_codes.contains(code)))
+    }
+
+    /// Returns `true` if the record describes a selected entity. Records
+    /// without a (valid) entity type are only selected by an empty
+    /// selector.
+    pub fn is_match_record(&self, record: &StringRecord) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        EntityType::from_record(record)
+            .map(|entity_type| self.is_match(&entity_type))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_selector() {
+        let entity_type = EntityType {
+            code: "Tp1".to_string(),
+            kind: ConceptKind::Person,
+            level: Some('1'),
+            entity_codes: vec!["piz".to_string()],
+        };
+
+        assert!(EntitySelector::default().is_match(&entity_type));
+
+        let selector: EntitySelector = toml::from_str(
+            r#"
+            kinds = ["person"]
+            levels = ["1", "2"]
+            entity_codes = ["piz", "saz"]
+            "#,
+        )
+        .unwrap();
+        assert!(selector.is_match(&entity_type));
+
+        let selector: EntitySelector =
+            toml::from_str(r#"entity_codes = ["wit"]"#).unwrap();
+        assert!(!selector.is_match(&entity_type));
+    }
+}
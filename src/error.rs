@@ -1,16 +1,146 @@
-use std::fmt;
+use std::error::Error as StdError;
+use std::path::PathBuf;
+use std::{fmt, io};
 
 #[derive(Debug)]
 pub enum Error {
-    Config(String),
-    Concept(String),
+    /// A record lacks a field, which is required to build the concept.
+    MissingField { idn: String, tag: String },
+    /// A record has no or a malformed IDN (`003@.0`).
+    InvalidIdn { idn: String },
+    /// The entity code (`002@.0`) of a record doesn't denote a supported
+    /// entity kind.
+    UnknownEntityKind { idn: String, code: String },
+    /// A regular expression of the config couldn't be compiled.
+    InvalidRegex {
+        pattern: String,
+        source: regex::Error,
+    },
+    /// A path expression of the config couldn't be parsed.
+    InvalidPath { path: String },
+    /// A config option has an invalid value.
+    InvalidValue { option: String, value: String },
+    /// The config file couldn't be read.
+    ConfigIo { path: PathBuf, source: io::Error },
+    /// The config file isn't valid TOML or doesn't match the config schema.
+    ConfigParse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// Adds the location of a record to an error. The position is the
+    /// 1-based number of the record among the valid records of the file;
+    /// invalid records, which are skipped by the reader, aren't counted.
+    Record {
+        file: Option<PathBuf>,
+        position: usize,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Adds the input file and the position of the record (1-based, among
+    /// the valid records of the file) to the error.
+    pub fn with_record_context(
+        self,
+        file: Option<PathBuf>,
+        position: usize,
+    ) -> Self {
+        Error::Record {
+            file,
+            position,
+            source: Box::new(self),
+        }
+    }
+
+    /// Returns the IDN of the record, which caused the error (if known).
+    pub fn idn(&self) -> Option<&str> {
+        match *self {
+            Error::MissingField { ref idn, .. }
+            | Error::InvalidIdn { ref idn }
+            | Error::UnknownEntityKind { ref idn, .. } => Some(idn),
+            Error::Record { ref source, .. } => source.idn(),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::Config(ref s) => f.write_str(s),
-            Error::Concept(ref s) => f.write_str(s),
+            Error::MissingField { ref idn, ref tag } => {
+                write!(f, "missing field '{}' in record '{}'", tag, idn)
+            }
+            Error::InvalidIdn { ref idn } if idn.is_empty() => {
+                f.write_str("record without IDN")
+            }
+            Error::InvalidIdn { ref idn } => write!(f, "invalid IDN '{}'", idn),
+            Error::UnknownEntityKind { ref idn, ref code } => {
+                write!(f, "unknown entity kind '{}' in record '{}'", code, idn)
+            }
+            Error::InvalidRegex { ref pattern, .. } => {
+                write!(f, "invalid regex '{}'", pattern)
+            }
+            Error::InvalidPath { ref path } => {
+                write!(f, "invalid path '{}'", path)
+            }
+            Error::InvalidValue {
+                ref option,
+                ref value,
+            } => write!(f, "invalid value '{}' of option '{}'", value, option),
+            Error::ConfigIo { ref path, .. } => {
+                write!(f, "unable to read config file '{}'", path.display())
+            }
+            Error::ConfigParse { ref path, .. } => {
+                write!(f, "unable to parse config file '{}'", path.display())
+            }
+            Error::Record {
+                ref file,
+                position,
+                ref source,
+            } => match file {
+                Some(file) => write!(
+                    f,
+                    "{} (valid record #{} in '{}')",
+                    source,
+                    position,
+                    file.display()
+                ),
+                None => write!(f, "{} (valid record #{})", source, position),
+            },
         }
     }
 }
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Error::InvalidRegex { ref source, .. } => Some(source),
+            Error::ConfigIo { ref source, .. } => Some(source),
+            Error::ConfigParse { ref source, .. } => Some(source),
+            Error::Record { ref source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_context() {
+        let err = Error::MissingField {
+            idn: "118515551".to_string(),
+            tag: "028A".to_string(),
+        }
+        .with_record_context(Some(PathBuf::from("dump.dat.gz")), 42);
+
+        assert_eq!(err.idn(), Some("118515551"));
+        assert_eq!(
+            err.to_string(),
+            "missing field '028A' in record '118515551' (valid record \
+             #42 in 'dump.dat.gz')"
+        );
+        assert!(err.source().is_some());
+    }
+}
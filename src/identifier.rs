@@ -0,0 +1,116 @@
+use pica::StringRecord;
+
+use crate::Config;
+
+/// Fields, which link a record to other authority files or systems. The
+/// system is given in `$S` (or `$a`) and the identifier in `$0`.
+const IDENTIFIER_FIELDS: [&str; 3] = ["006X", "006Y", "007K"];
+
+/// Systems, whose identifiers are skipped. The `gnd` identifier in `007K`
+/// identifies the record itself.
+const SKIPPED_SYSTEMS: [&str; 1] = ["gnd"];
+
+/// An identifier of a concept in an external system (e.g. VIAF, Wikidata).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identifier {
+    system: String,
+    value: String,
+}
+
+impl Identifier {
+    pub fn new<S>(system: S, value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            system: system.into(),
+            value: value.into(),
+        }
+    }
+
+    pub(crate) fn from_record(record: &StringRecord) -> Vec<Identifier> {
+        IDENTIFIER_FIELDS
+            .iter()
+            .flat_map(|tag| record.all(tag).unwrap_or_default())
+            .filter_map(|field| {
+                let system = field.first('S').or_else(|| field.first('a'))?;
+                let system = system.to_string().to_lowercase();
+                if SKIPPED_SYSTEMS.contains(&system.as_str()) {
+                    return None;
+                }
+
+                let value = field.first('0')?;
+                Some(Identifier::new(system, value.to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns the (lowercase) name of the external system.
+    pub fn system(&self) -> &str {
+        &self.system
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the URI of the identifier. Values, which are already URIs,
+    /// are returned unchanged; otherwise the URI template of the system is
+    /// used. Returns `None` if there is no template for the system.
+    pub fn uri(&self, config: &Config) -> Option<String> {
+        if self.value.starts_with("http://")
+            || self.value.starts_with("https://")
+        {
+            return Some(self.value.to_owned());
+        }
+
+        config
+            .identifier
+            .templates
+            .get(&self.system)
+            .map(|template| template.replace("{}", &self.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_uri() {
+        let mut config = Config::default();
+        config
+            .identifier
+            .templates
+            .insert("viaf".to_string(), "http://viaf.org/viaf/{}".to_string());
+
+        let identifier = Identifier::new("viaf", "12345");
+        assert_eq!(
+            identifier.uri(&config),
+            Some("http://viaf.org/viaf/12345".to_string())
+        );
+
+        let identifier = Identifier::new("isni", "0000000121020221");
+        assert_eq!(identifier.uri(&config), None);
+
+        let identifier =
+            Identifier::new("wikidata", "http://www.wikidata.org/entity/Q84");
+        assert_eq!(
+            identifier.uri(&config),
+            Some("http://www.wikidata.org/entity/Q84".to_string())
+        );
+    }
+
+    #[test]
+    fn test_identifier_from_record() {
+        let record = crate::parse_record(
+            "003@ \x1f0118515551\x1e007K \x1fagnd\x1f0118515551\x1e\
+             006X \x1fSVIAF\x1f0100180950\x1e\n",
+        );
+
+        assert_eq!(
+            Identifier::from_record(&record),
+            vec![Identifier::new("viaf", "100180950")]
+        );
+    }
+}
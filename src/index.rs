@@ -0,0 +1,420 @@
+use std::collections::{HashMap, HashSet};
+
+use pica::StringRecord;
+
+use crate::concept::{self, ConceptBuilder};
+use crate::subject_term::SubjectTermBuilder;
+use crate::{Concept, ConceptKind, Config, Relation, RelationKind, SynKind};
+
+/// Collects the URIs and outgoing relations of all processed concepts in
+/// order to derive the relations, which can't be built from a single
+/// record.
+#[derive(Debug, Default)]
+pub struct RelationIndex {
+    concepts: HashSet<String>,
+    relations: Vec<(String, Relation)>,
+}
+
+impl RelationIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_concept(&mut self, concept: &Concept) {
+        self.concepts.insert(concept.uri().to_owned());
+
+        for relation in concept.relations() {
+            self.relations
+                .push((concept.uri().to_owned(), relation.clone()));
+        }
+    }
+
+    /// Adds the URI of the concept described by the record without building
+    /// the concept. This allows to check the relations of a concept against
+    /// concepts, which occur later in the input.
+    pub fn add_record(&mut self, record: &StringRecord, config: &Config) {
+        if let Ok(uri) = SubjectTermBuilder::uri(record, config) {
+            self.concepts.insert(uri);
+        }
+    }
+
+    /// Returns `true` if a concept with the given URI was processed.
+    pub fn contains(&self, uri: &str) -> bool {
+        self.concepts.contains(uri)
+    }
+
+    /// Returns the inverse relations of all relations between processed
+    /// concepts: a `Narrower` relation for each `Broader` relation (and
+    /// vice versa) and a `Related` relation for each `Related` relation,
+    /// which has no counterpart yet. Only symmetric relations keep their
+    /// relation code; the inverse of a directed relation (e.g. `beru`) is a
+    /// plain `skos:related`.
+    pub fn inverse_relations(&self) -> Vec<(String, Relation)> {
+        let existing = self
+            .relations
+            .iter()
+            .map(|(source, r)| (source.as_str(), r.uri(), r.kind))
+            .collect::<HashSet<_>>();
+
+        let mut seen = HashSet::new();
+        let mut result = vec![];
+
+        for (source, relation) in self.relations.iter() {
+            if !self.contains(relation.uri()) {
+                continue;
+            }
+
+            let inverse = match relation.kind {
+                RelationKind::Broader => {
+                    Relation::new(source.as_str(), RelationKind::Narrower, "")
+                }
+                RelationKind::Narrower => {
+                    Relation::new(source.as_str(), RelationKind::Broader, "")
+                }
+                RelationKind::Related => Relation::new(
+                    source.as_str(),
+                    RelationKind::Related,
+                    if relation.is_symmetric() {
+                        relation.code()
+                    } else {
+                        ""
+                    },
+                ),
+            };
+
+            let key = (relation.uri(), source.as_str(), inverse.kind);
+            if existing.contains(&key) || !seen.insert(key) {
+                continue;
+            }
+
+            result.push((relation.uri().to_owned(), inverse));
+        }
+
+        result
+    }
+
+    /// Returns all relations, whose target wasn't processed.
+    pub fn dangling_relations(
+        &self,
+    ) -> impl Iterator<Item = &(String, Relation)> {
+        self.relations
+            .iter()
+            .filter(|(_, relation)| !self.contains(relation.uri()))
+    }
+}
+
+/// Maps the labels of all processed concepts to the URIs of the concepts
+/// in order to find headings without qualifiers and preferred labels, which
+/// are shared by several concepts, and to look up superior corporate bodies
+/// by name.
+#[derive(Debug, Default)]
+pub struct LabelIndex {
+    labels: HashMap<String, Vec<String>>,
+    unqualified: HashSet<String>,
+    preferred: HashMap<String, Vec<String>>,
+    corporate_bodies: HashMap<String, Vec<String>>,
+}
+
+impl LabelIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_concept(&mut self, concept: &Concept) {
+        let labels = concept
+            .synset()
+            .iter()
+            .map(|synonym| {
+                if synonym.is_unqualified() {
+                    self.unqualified.insert(synonym.label().to_owned());
+                }
+
+                if *synonym.kind() == SynKind::Preferred {
+                    self.preferred
+                        .entry(synonym.label().to_owned())
+                        .or_insert_with(Vec::new)
+                        .push(concept.uri().to_owned());
+                }
+
+                if *concept.kind() == ConceptKind::CorporateBody
+                    && *synonym.kind() != SynKind::Hidden
+                {
+                    let uris = self
+                        .corporate_bodies
+                        .entry(synonym.label().to_owned())
+                        .or_insert_with(Vec::new);
+
+                    if !uris.iter().any(|uri| uri == concept.uri()) {
+                        uris.push(concept.uri().to_owned());
+                    }
+                }
+
+                synonym.label()
+            })
+            .collect::<HashSet<&str>>();
+
+        for label in labels {
+            self.labels
+                .entry(label.to_owned())
+                .or_insert_with(Vec::new)
+                .push(concept.uri().to_owned());
+        }
+    }
+
+    /// Returns the URIs of all corporate bodies with the given preferred or
+    /// alternative label.
+    pub fn get_corporate_body(&self, label: &str) -> &[String] {
+        self.corporate_bodies
+            .get(label)
+            .map(|uris| uris.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the label belongs to more than one concept.
+    pub fn is_ambiguous(&self, label: &str) -> bool {
+        self.labels
+            .get(label)
+            .map(|uris| uris.len() > 1)
+            .unwrap_or_default()
+    }
+
+    /// Returns all headings without qualifiers (in lexical order), which
+    /// are shared by several concepts, and the URIs of these concepts.
+    pub fn ambiguous_unqualified(&self) -> Vec<(&str, &Vec<String>)> {
+        let mut result = self
+            .unqualified
+            .iter()
+            .filter_map(|label| {
+                self.labels
+                    .get(label)
+                    .filter(|uris| uris.len() > 1)
+                    .map(|uris| (label.as_str(), uris))
+            })
+            .collect::<Vec<_>>();
+
+        result.sort_unstable();
+        result
+    }
+
+    /// Returns `true` if the label is the preferred label of more than one
+    /// concept.
+    pub fn is_ambiguous_preferred(&self, label: &str) -> bool {
+        self.preferred
+            .get(label)
+            .map(|uris| uris.len() > 1)
+            .unwrap_or_default()
+    }
+
+    /// Returns all preferred labels (in lexical order), which are shared by
+    /// several concepts, and the URIs of these concepts.
+    pub fn ambiguous_preferred(&self) -> Vec<(&str, &Vec<String>)> {
+        let mut result = self
+            .preferred
+            .iter()
+            .filter(|(_, uris)| uris.len() > 1)
+            .map(|(label, uris)| (label.as_str(), uris))
+            .collect::<Vec<_>>();
+
+        result.sort_unstable();
+        result
+    }
+}
+
+/// Maps the IDNs of records to their GND-IDs.
+#[derive(Debug, Default)]
+pub struct GndIdIndex {
+    ids: HashMap<String, String>,
+}
+
+impl GndIdIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_record(&mut self, record: &StringRecord) {
+        if let Some(gnd_id) = concept::gnd_id(record) {
+            self.ids.insert(concept::idn(record), gnd_id);
+        }
+    }
+
+    /// Returns the GND-ID of the record with the given IDN.
+    pub fn get(&self, idn: &str) -> Option<&str> {
+        self.ids.get(idn).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Synonym;
+
+    #[test]
+    fn test_inverse_relations() {
+        let mut index = RelationIndex::new();
+        index.add_concept(&Concept::new(
+            "a",
+            vec![
+                Relation::new("b", RelationKind::Broader, "obge"),
+                Relation::new("c", RelationKind::Related, "vbal"),
+                Relation::new("x", RelationKind::Related, "vbal"),
+            ],
+            ConceptKind::SubjectTerm,
+        ));
+        index.add_concept(&Concept::new("b", vec![], ConceptKind::SubjectTerm));
+        index.add_concept(&Concept::new(
+            "c",
+            vec![Relation::new("a", RelationKind::Related, "vbal")],
+            ConceptKind::SubjectTerm,
+        ));
+
+        let inverse = index.inverse_relations();
+        assert_eq!(inverse.len(), 1);
+        assert_eq!(inverse[0].0, "b");
+        assert_eq!(inverse[0].1.uri(), "a");
+        assert_eq!(inverse[0].1.kind(), &RelationKind::Narrower);
+
+        let dangling = index.dangling_relations().collect::<Vec<_>>();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].1.uri(), "x");
+    }
+
+    #[test]
+    fn test_inverse_directed_relations() {
+        let config = Config::default();
+        let mut index = RelationIndex::new();
+        index.add_concept(&Concept::new(
+            "broch",
+            vec![
+                Relation::new("schriftsteller", RelationKind::Related, "beru"),
+                Relation::new("canetti", RelationKind::Related, "beza"),
+            ],
+            ConceptKind::Person,
+        ));
+        index.add_concept(&Concept::new(
+            "schriftsteller",
+            vec![],
+            ConceptKind::SubjectTerm,
+        ));
+        index.add_concept(&Concept::new(
+            "canetti",
+            vec![],
+            ConceptKind::Person,
+        ));
+
+        let inverse = index.inverse_relations();
+        assert_eq!(inverse.len(), 2);
+
+        let (subject, relation) = &inverse[0];
+        assert_eq!(subject, "schriftsteller");
+        assert_eq!(relation.uri(), "broch");
+        assert_eq!(
+            relation.gndo_predicate(&config),
+            "http://www.w3.org/2004/02/skos/core#related"
+        );
+
+        let (subject, relation) = &inverse[1];
+        assert_eq!(subject, "canetti");
+        assert_eq!(
+            relation.gndo_predicate(&config),
+            "https://d-nb.info/standards/elementset/gnd#\
+             acquaintanceshipOrFriendship"
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_unqualified() {
+        let mut a = Concept::new("a", vec![], ConceptKind::Place);
+        a.add_synonym(Synonym::new("Berlin", SynKind::Preferred));
+
+        let mut b = Concept::new("b", vec![], ConceptKind::Place);
+        b.add_synonym(Synonym::new("Berlin (West)", SynKind::Preferred));
+        b.add_synonym(
+            Synonym::builder(SynKind::Hidden)
+                .unqualified(true)
+                .push_str("Berlin")
+                .build()
+                .unwrap(),
+        );
+
+        let mut index = LabelIndex::new();
+        index.add_concept(&a);
+        index.add_concept(&b);
+
+        assert!(index.is_ambiguous("Berlin"));
+        assert!(!index.is_ambiguous("Berlin (West)"));
+
+        let ambiguous = index.ambiguous_unqualified();
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].0, "Berlin");
+        assert_eq!(ambiguous[0].1, &vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_ambiguous_preferred() {
+        let mut a = Concept::new("a", vec![], ConceptKind::Person);
+        a.add_synonym(Synonym::new("Müller, Hans", SynKind::Preferred));
+
+        let mut b = Concept::new("b", vec![], ConceptKind::Person);
+        b.add_synonym(Synonym::new("Müller, Hans", SynKind::Preferred));
+
+        let mut c = Concept::new("c", vec![], ConceptKind::Person);
+        c.add_synonym(Synonym::new(
+            "Müller, Hans (1900-1980)",
+            SynKind::Preferred,
+        ));
+        c.add_synonym(Synonym::new("Müller, Hans", SynKind::Hidden));
+
+        let mut index = LabelIndex::new();
+        index.add_concept(&a);
+        index.add_concept(&b);
+        index.add_concept(&c);
+
+        assert!(index.is_ambiguous_preferred("Müller, Hans"));
+        assert!(!index.is_ambiguous_preferred("Müller, Hans (1900-1980)"));
+
+        let ambiguous = index.ambiguous_preferred();
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].1, &vec!["a".to_string(), "b".to_string()]);
+
+        b.disambiguate_pref_label();
+        let labels = b
+            .synset()
+            .iter()
+            .map(|synonym| (synonym.label(), synonym.kind().clone()))
+            .collect::<HashSet<_>>();
+        assert!(labels.contains(&("Müller, Hans [b]", SynKind::Preferred)));
+        assert!(labels.contains(&("Müller, Hans", SynKind::Hidden)));
+    }
+
+    #[test]
+    fn test_get_corporate_body() {
+        let mut place = Concept::new("p", vec![], ConceptKind::Place);
+        place.add_synonym(Synonym::new("Bayern", SynKind::Preferred));
+
+        let mut body = Concept::new("b", vec![], ConceptKind::CorporateBody);
+        body.add_synonym(Synonym::new(
+            "Bayern / Staatsministerium",
+            SynKind::Preferred,
+        ));
+        body.add_synonym(Synonym::new("Staatsministerium", SynKind::Hidden));
+
+        let mut index = LabelIndex::new();
+        index.add_concept(&place);
+        index.add_concept(&body);
+
+        assert!(index.get_corporate_body("Bayern").is_empty());
+        assert!(index.get_corporate_body("Staatsministerium").is_empty());
+        assert_eq!(
+            index.get_corporate_body("Bayern / Staatsministerium"),
+            &["b".to_string()]
+        );
+
+        let mut unit = Concept::new("u", vec![], ConceptKind::CorporateBody);
+        unit.add_superior("b");
+        assert_eq!(unit.relations()[0].code(), "adue");
+        assert_eq!(
+            unit.relations()[0].target_kind(),
+            Some(ConceptKind::CorporateBody)
+        );
+    }
+}
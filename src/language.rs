@@ -0,0 +1,259 @@
+use pica_core::Field;
+
+/// ISO 639-2/B codes (`$L`) and the corresponding BCP-47 language subtags.
+/// Codes without an entry are used unchanged.
+const LANGUAGE_CODES: [(&str, &str); 39] = [
+    ("ara", "ar"),
+    ("arm", "hy"),
+    ("bul", "bg"),
+    ("chi", "zh"),
+    ("cze", "cs"),
+    ("dan", "da"),
+    ("dut", "nl"),
+    ("eng", "en"),
+    ("est", "et"),
+    ("fin", "fi"),
+    ("fre", "fr"),
+    ("geo", "ka"),
+    ("ger", "de"),
+    ("gre", "el"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hrv", "hr"),
+    ("hun", "hu"),
+    ("ita", "it"),
+    ("jpn", "ja"),
+    ("kor", "ko"),
+    ("lat", "la"),
+    ("lav", "lv"),
+    ("lit", "lt"),
+    ("nor", "no"),
+    ("per", "fa"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("rum", "ro"),
+    ("rus", "ru"),
+    ("slo", "sk"),
+    ("slv", "sl"),
+    ("spa", "es"),
+    ("srp", "sr"),
+    ("swe", "sv"),
+    ("tha", "th"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+    ("yid", "yi"),
+];
+
+/// Languages, which aren't written in the Latin script by default. The
+/// script subtag is omitted if it matches the default script.
+const DEFAULT_SCRIPTS: [(&str, &str); 17] = [
+    ("ar", "Arab"),
+    ("bg", "Cyrl"),
+    ("el", "Grek"),
+    ("fa", "Arab"),
+    ("he", "Hebr"),
+    ("hi", "Deva"),
+    ("hy", "Armn"),
+    ("ja", "Jpan"),
+    ("ka", "Geor"),
+    ("ko", "Kore"),
+    ("ru", "Cyrl"),
+    ("sr", "Cyrl"),
+    ("th", "Thai"),
+    ("uk", "Cyrl"),
+    ("ur", "Arab"),
+    ("yi", "Hebr"),
+    ("zh", "Hani"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Arabic,
+    Armenian,
+    Cyrillic,
+    Devanagari,
+    Georgian,
+    Greek,
+    Han,
+    Hangul,
+    Hebrew,
+    Kana,
+    Latin,
+    Thai,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Script> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+
+        Some(match c as u32 {
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0400..=0x052F => Script::Cyrillic,
+            0x0530..=0x058F => Script::Armenian,
+            0x0590..=0x05FF | 0xFB1D..=0xFB4F => Script::Hebrew,
+            0x0600..=0x06FF
+            | 0x0750..=0x077F
+            | 0xFB50..=0xFDFF
+            | 0xFE70..=0xFEFF => Script::Arabic,
+            0x0900..=0x097F => Script::Devanagari,
+            0x0E00..=0x0E7F => Script::Thai,
+            0x10A0..=0x10FF => Script::Georgian,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => {
+                Script::Hangul
+            }
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Kana,
+            0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xF900..=0xFAFF
+            | 0x20000..=0x2FFFF => Script::Han,
+            _ => Script::Latin,
+        })
+    }
+
+    /// Returns the language tag, which is assumed for a label in this
+    /// script, if the record doesn't state the language.
+    fn language_tag(&self) -> Option<&'static str> {
+        match self {
+            Script::Arabic => Some("und-Arab"),
+            Script::Armenian => Some("hy"),
+            Script::Cyrillic => Some("und-Cyrl"),
+            Script::Devanagari => Some("und-Deva"),
+            Script::Georgian => Some("ka"),
+            Script::Greek => Some("el"),
+            Script::Han => Some("und-Hani"),
+            Script::Hangul => Some("ko-Hang"),
+            Script::Hebrew => Some("he"),
+            Script::Kana => Some("ja"),
+            Script::Thai => Some("th"),
+            Script::Latin => None,
+        }
+    }
+}
+
+/// Returns `true` if the value consists of `min` to `max` ASCII letters.
+fn is_subtag(value: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&value.len())
+        && value.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+/// Returns the BCP-47 language tag of a field, which is built from the
+/// language (`$L`) and script (`$U`) subfields. Returns `None`, if the
+/// subfields don't yield a well-formed language and script subtag.
+pub(crate) fn from_field(field: &Field) -> Option<String> {
+    let code = field.first('L')?.to_string().to_lowercase();
+    if !is_subtag(&code, 2, 8) {
+        return None;
+    }
+
+    let language = LANGUAGE_CODES
+        .iter()
+        .find(|(iso639_2, _)| *iso639_2 == code)
+        .map(|(_, tag)| tag.to_string())
+        .unwrap_or(code);
+
+    let default_script = DEFAULT_SCRIPTS
+        .iter()
+        .find(|(tag, _)| *tag == language)
+        .map(|(_, script)| *script)
+        .unwrap_or("Latn");
+
+    match field
+        .first('U')
+        .map(|script| titlecase(&script.to_string()))
+    {
+        Some(script) if !is_subtag(&script, 4, 4) => None,
+        Some(script) if script != default_script => {
+            Some(format!("{}-{}", language, script))
+        }
+        _ => Some(language),
+    }
+}
+
+fn titlecase(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// Guesses the language tag of a label from the script of its letters.
+/// Returns `None` for labels in the Latin script, because the script says
+/// nothing about the language.
+pub(crate) fn detect(label: &str) -> Option<&'static str> {
+    let mut counts: Vec<(Script, usize)> = vec![];
+
+    for script in label.chars().filter_map(Script::of) {
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+
+    // Japanese labels mix Kana and Han characters.
+    if counts.iter().any(|(script, _)| *script == Script::Kana) {
+        return Script::Kana.language_tag();
+    }
+
+    counts
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .and_then(|(script, _)| script.language_tag())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect("ברוך, הרמן"), Some("he"));
+        assert_eq!(detect("브로흐, 헤르만"), Some("ko-Hang"));
+        assert_eq!(detect("ヘルマン・ブロッホ"), Some("ja"));
+        assert_eq!(detect("Broch, Hermann (1886-1951)"), None);
+        assert_eq!(detect("1886"), None);
+    }
+
+    #[test]
+    fn test_from_field() {
+        let record = crate::parse_record(
+            "003@ \x1f0123\x1e\
+             028@ \x1faBroch\x1fLger\x1fUlatn\x1e\
+             028@ \x1faBroch\x1fLrus\x1fUCyrl\x1e\
+             028@ \x1faBroch\x1fLkor\x1fUHang\x1e\
+             028@ \x1faBroch\x1fLger@\x1e\
+             028@ \x1faBroch\x1fLger\x1fULatin\x1e\
+             028@ \x1faBroch\x1e\n",
+        );
+
+        let tags = record
+            .all("028@")
+            .unwrap()
+            .into_iter()
+            .map(from_field)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tags,
+            vec![
+                Some("de".to_string()),
+                Some("ru".to_string()),
+                Some("ko-Hang".to_string()),
+                None,
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_titlecase() {
+        assert_eq!(titlecase("HANG"), "Hang");
+        assert_eq!(titlecase("latn"), "Latn");
+    }
+}
@@ -1,21 +1,50 @@
+mod biographical;
 mod collection;
 mod concept;
 mod conference;
 mod config;
 mod corporate_body;
+mod entity_type;
 mod error;
+mod identifier;
+mod index;
+mod language;
 mod person;
 mod place;
 mod relation;
 mod subject_term;
+mod synonym_rule;
 mod synset;
+mod translit;
 mod work;
 
+pub use biographical::{BiographicalData, RelatedEntity};
 pub use collection::Collection;
 pub use concept::{Concept, ConceptKind};
-pub use config::Config;
+pub use config::{
+    AmbiguousLabels, Config, DanglingRelations, LinkPredicate, OutputProfile,
+    QuoteStyle, RdfFormat, TableFormat, UriSource,
+};
+pub use entity_type::{EntitySelector, EntityType};
 pub use error::Error;
+pub use identifier::Identifier;
+pub use index::{GndIdIndex, LabelIndex, RelationIndex};
 pub use relation::{Relation, RelationKind};
+pub use synonym_rule::{Pattern, SynonymRule};
 pub use synset::{SynKind, SynSet, Synonym};
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Parses a single (normalized) PICA+ record in tests.
+#[cfg(test)]
+pub(crate) fn parse_record(data: &str) -> pica::StringRecord {
+    use std::io::Cursor;
+    use std::path::Path;
+
+    pica::ReaderBuilder::new()
+        .from_reader(Cursor::new(data.as_bytes().to_vec()), None::<&Path>)
+        .records()
+        .next()
+        .unwrap()
+        .unwrap()
+}
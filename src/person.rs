@@ -1,32 +1,76 @@
 use crate::concept::ConceptBuilder;
 use crate::config::TranslitChoice;
 use crate::synset::SynonymBuilder;
-use crate::{Concept, ConceptKind, Config, Result, SynKind, Synonym};
+use crate::{
+    language, BiographicalData, Concept, ConceptKind, Config, Result, SynKind,
+    Synonym,
+};
 
 use lazy_static::lazy_static;
-use pica::matcher::{FieldMatcher, MatcherFlags};
 use pica::StringRecord;
 use pica_core::Field;
 use regex::Regex;
 
 pub(crate) struct PersonBuilder;
 
-fn get_biographical_data(record: &StringRecord) -> Option<String> {
-    let matcher = FieldMatcher::new("060R.4 == 'datl'").unwrap();
-    let flags = MatcherFlags::default();
-
-    if let Some(field) =
-        record.iter().find(|field| matcher.is_match(field, &flags))
-    {
-        match (field.first('a'), field.first('b')) {
-            (Some(from), Some(to)) => Some(format!(" ({}-{})", from, to)),
-            (Some(from), None) => Some(format!(" ({})", from)),
-            (None, Some(to)) => Some(format!(" ({})", to)),
-            _ => None,
-        }
-    } else {
-        None
+/// Formats a pair of dates, e.g. "1886-1951".
+fn format_dates(from: Option<&str>, to: Option<&str>) -> Option<String> {
+    match (from, to) {
+        (Some(from), Some(to)) => Some(format!("{}-{}", from, to)),
+        (Some(from), None) => Some(from.to_string()),
+        (None, Some(to)) => Some(to.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the free-text dates of activity or the time period of a person
+/// (`060R` with `$4 datw` or `datz`).
+fn get_period(record: &StringRecord) -> Option<String> {
+    record
+        .all("060R")
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|field| {
+            let code = field.first('4').map(|code| code.to_string());
+            matches!(code.as_deref(), Some("datw" | "datz"))
+        })
+        .find_map(|field| {
+            field.first('c').map(|text| text.to_string()).or_else(|| {
+                let from = field.first('a').map(|value| value.to_string());
+                let to = field.first('b').map(|value| value.to_string());
+                format_dates(from.as_deref(), to.as_deref())
+            })
+        })
+}
+
+/// Returns the qualifier of the preferred label: the life dates of the
+/// person. If there are no life dates and `fallback` is set, the
+/// dates of activity, the first profession or the free-text dates of
+/// activity or time period are used instead.
+fn get_biographical_data(
+    record: &StringRecord,
+    data: Option<&BiographicalData>,
+    fallback: bool,
+) -> Option<String> {
+    let life_dates = data.and_then(|data| {
+        format_dates(data.date_of_birth(), data.date_of_death())
+    });
+
+    if life_dates.is_some() || !fallback {
+        return life_dates.map(|dates| format!(" ({})", dates));
     }
+
+    data.and_then(|data| {
+        format_dates(data.activity_start(), data.activity_end())
+            .map(|dates| format!("Wirkungszeit {}", dates))
+            .or_else(|| {
+                data.professions()
+                    .first()
+                    .map(|profession| profession.label().to_string())
+            })
+    })
+    .or_else(|| get_period(record))
+    .map(|qualifier| format!(" ({})", qualifier))
 }
 
 pub(crate) fn get_synonym(
@@ -34,14 +78,16 @@ pub(crate) fn get_synonym(
     kind: SynKind,
     translit: Option<&TranslitChoice>,
     min_length: usize,
-    synonym_filter: Option<&String>,
+    synonym_filter: Option<&Regex>,
     no_initials: bool,
     no_modern_names: bool,
 ) -> Option<Synonym> {
     let mut synonym = Synonym::builder(kind)
         .translit(translit)
         .min_length(min_length)
-        .filter(synonym_filter);
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field));
 
     if field.contains_code('a') {
         if no_initials && field.contains_code('d') {
@@ -91,14 +137,17 @@ impl ConceptBuilder for PersonBuilder {
         let no_initials = config.concept.person_no_initials.unwrap_or_default();
         let mut no_modern_names =
             config.concept.person_no_modern_names.unwrap_or_default();
-        let synonym_filter = config.concept.synonym_filter.as_ref();
+        let synonym_filter = config.concept.synonym_filter.as_deref();
         let translit = config.concept.translit.as_ref();
+        let fallback = config.concept.person_label_fallback.unwrap_or_default();
 
+        let field = Self::required_field(record, "028A")?;
         let mut concept = Concept::new(
             Self::uri(record, config)?,
             Self::relations(record, config),
             ConceptKind::Person,
         );
+        concept.biographical = BiographicalData::from_record(record, config);
 
         lazy_static! {
             static ref RE: Regex =
@@ -106,7 +155,7 @@ impl ConceptBuilder for PersonBuilder {
         }
 
         if let Some(synonym) = get_synonym(
-            record.first("028A").unwrap(),
+            field,
             SynKind::Preferred,
             translit,
             min_length,
@@ -117,6 +166,8 @@ impl ConceptBuilder for PersonBuilder {
             if let Some(captures) = RE.captures(synonym.label()) {
                 if let Some(hidden_label) = SynonymBuilder::new(SynKind::Hidden)
                     .translit(translit)
+                    .source(synonym.source())
+                    .language(synonym.language())
                     .push_str(format!(
                         "{} {}",
                         captures.get(2).unwrap().as_str(),
@@ -128,8 +179,11 @@ impl ConceptBuilder for PersonBuilder {
                 }
             }
 
-            if let Some(biographical_data) = get_biographical_data(record) {
+            if let Some(biographical_data) =
+                get_biographical_data(record, concept.biographical(), fallback)
+            {
                 if let Some(pref_label) = SynonymBuilder::from(&synonym)
+                    .translit(translit)
                     .push_str(biographical_data)
                     .build()
                 {
@@ -147,7 +201,6 @@ impl ConceptBuilder for PersonBuilder {
             }
         }
 
-        let field = record.first("028A").unwrap();
         if !(field.contains_code('d') && field.contains_code('a')) {
             no_modern_names = false;
         }
@@ -166,6 +219,8 @@ impl ConceptBuilder for PersonBuilder {
                     if let Some(hidden_label) =
                         SynonymBuilder::new(SynKind::Hidden)
                             .translit(translit)
+                            .source(synonym.source())
+                            .language(synonym.language())
                             .push_str(format!(
                                 "{} {}",
                                 captures.get(2).unwrap().as_str(),
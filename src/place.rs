@@ -1,8 +1,9 @@
 use crate::concept::ConceptBuilder;
 use crate::config::TranslitChoice;
-use crate::{Concept, ConceptKind, Config, Result, SynKind, Synonym};
+use crate::{language, Concept, ConceptKind, Config, Result, SynKind, Synonym};
 use pica::StringRecord;
 use pica_core::Field;
+use regex::Regex;
 
 pub(crate) struct PlaceBuilder;
 
@@ -11,19 +12,21 @@ pub(crate) fn get_synonym(
     kind: SynKind,
     translit: Option<&TranslitChoice>,
     min_length: usize,
-    synonym_filter: Option<&String>,
+    synonym_filter: Option<&Regex>,
 ) -> Option<Synonym> {
     let mut synonym = Synonym::builder(kind)
         .translit(translit)
         .min_length(min_length)
-        .filter(synonym_filter);
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field));
 
     for subfield in field.iter() {
         let value = subfield.value().to_string();
 
         match subfield.code() {
             'a' => {
-                synonym = synonym.push_str(value.replace('@', ""));
+                synonym = synonym.push_str(strip_non_sorting(value));
             }
             'g' | 'z' => {
                 synonym = synonym.push_str(&format!(" ({})", value));
@@ -38,11 +41,61 @@ pub(crate) fn get_synonym(
     synonym.build()
 }
 
+/// Returns the heading without the qualifiers (e.g. `$g`, `$z`) as hidden
+/// label, if the heading has one of the given qualifiers. The name (`$a`)
+/// is passed through `name`, so that it is normalized the same way as in
+/// the other labels of the concept.
+pub(crate) fn get_unqualified_synonym(
+    field: &Field,
+    qualifiers: &[char],
+    name: fn(String) -> String,
+    translit: Option<&TranslitChoice>,
+    min_length: usize,
+    synonym_filter: Option<&Regex>,
+) -> Option<Synonym> {
+    if !field.contains_code('a')
+        || !qualifiers.iter().any(|code| field.contains_code(*code))
+    {
+        return None;
+    }
+
+    let mut synonym = Synonym::builder(SynKind::Hidden)
+        .translit(translit)
+        .min_length(min_length)
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field))
+        .unqualified(true);
+
+    for subfield in field.iter() {
+        let value = subfield.value().to_string();
+
+        match subfield.code() {
+            'a' => {
+                synonym = synonym.push_str(name(value));
+            }
+            'x' => {
+                synonym = synonym.push_str(&format!(" / {}", value));
+            }
+            _ => continue,
+        }
+    }
+
+    synonym.build()
+}
+
+/// Removes the non-sorting character (`@`) from the name of a place.
+fn strip_non_sorting(name: String) -> String {
+    name.replace('@', "")
+}
+
 impl ConceptBuilder for PlaceBuilder {
     fn from_record(record: &StringRecord, config: &Config) -> Result<Concept> {
         let min_length = config.concept.min_synonym_length.unwrap_or_default();
-        let synonym_filter = config.concept.synonym_filter.as_ref();
+        let synonym_filter = config.concept.synonym_filter.as_deref();
         let translit = config.concept.translit.as_ref();
+        let strip_qualifiers =
+            config.concept.strip_qualifiers.unwrap_or_default();
 
         let mut concept = Concept::new(
             Self::uri(record, config)?,
@@ -50,8 +103,9 @@ impl ConceptBuilder for PlaceBuilder {
             ConceptKind::Place,
         );
 
+        let field = Self::required_field(record, "065A")?;
         if let Some(synonym) = get_synonym(
-            record.first("065A").unwrap(),
+            field,
             SynKind::Preferred,
             translit,
             min_length,
@@ -60,6 +114,8 @@ impl ConceptBuilder for PlaceBuilder {
             concept.add_synonym(synonym);
         }
 
+        let mut fields = vec![field];
+
         for field in record.all("065@").unwrap_or_default() {
             if let Some(synonym) = get_synonym(
                 field,
@@ -70,8 +126,67 @@ impl ConceptBuilder for PlaceBuilder {
             ) {
                 concept.add_synonym(synonym);
             }
+
+            fields.push(field);
+        }
+
+        if strip_qualifiers {
+            for field in fields {
+                if let Some(synonym) = get_unqualified_synonym(
+                    field,
+                    &['g', 'z'],
+                    strip_non_sorting,
+                    translit,
+                    min_length,
+                    synonym_filter,
+                ) {
+                    concept.add_synonym_if_new(synonym);
+                }
+            }
         }
 
         Ok(concept)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_unqualified_synonym() {
+        let record = crate::parse_record(
+            "003@ \x1f0123\x1e065A \x1faFrankfurt@\x1fgOder\x1fxSüd\x1e\n",
+        );
+        let field = record.first("065A").unwrap();
+
+        let synonym = get_unqualified_synonym(
+            field,
+            &['g', 'z'],
+            strip_non_sorting,
+            None,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(synonym.label(), "Frankfurt / Süd");
+        assert_eq!(synonym.kind(), &SynKind::Hidden);
+        assert!(synonym.is_unqualified());
+
+        // Subject terms keep the name unchanged.
+        let synonym =
+            get_unqualified_synonym(field, &['g'], |name| name, None, 0, None)
+                .unwrap();
+        assert_eq!(synonym.label(), "Frankfurt@ / Süd");
+
+        assert!(get_unqualified_synonym(
+            field,
+            &['z'],
+            strip_non_sorting,
+            None,
+            0,
+            None,
+        )
+        .is_none());
+    }
+}
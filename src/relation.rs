@@ -1,19 +1,116 @@
 use std::convert::Infallible;
+use std::fmt;
 use std::str::FromStr;
 
+use lazy_static::lazy_static;
 use pica_core::Field;
+use regex::Regex;
 
-#[derive(Debug)]
+use crate::{ConceptKind, Config};
+
+const SKOS_NS: &str = "http://www.w3.org/2004/02/skos/core#";
+const GNDO_NS: &str = "https://d-nb.info/standards/elementset/gnd#";
+
+lazy_static! {
+    /// An absolute IRI: a scheme followed by characters, which are allowed
+    /// in an IRI.
+    static ref IRI_RE: Regex =
+        Regex::new(r#"^[A-Za-z][A-Za-z0-9+.-]*:[^\s<>"{}|\\^`]+$"#).unwrap();
+}
+
+/// Expands a predicate of the `[relation]` mapping, which is either a full
+/// URI or a name prefixed by `skos:` or `gndo:`.
+fn expand_predicate(predicate: &str) -> String {
+    if let Some(name) = predicate.strip_prefix("skos:") {
+        return String::from(SKOS_NS) + name;
+    }
+
+    if let Some(name) = predicate.strip_prefix("gndo:") {
+        return String::from(GNDO_NS) + name;
+    }
+
+    predicate.to_owned()
+}
+
+/// Returns `true` if the predicate of the `[relation]` mapping expands to
+/// an absolute IRI.
+pub(crate) fn is_valid_predicate(predicate: &str) -> bool {
+    IRI_RE.is_match(&expand_predicate(predicate))
+}
+
+/// Relation codes (`$4`) of the 0xxR fields, the kind of the relation and
+/// the corresponding property of the GND Ontology (if there is one).
+const RELATION_CODES: [(&str, RelationKind, Option<&str>); 31] = [
+    ("obal", RelationKind::Broader, Some("broaderTermGeneral")),
+    ("obge", RelationKind::Broader, Some("broaderTermGeneric")),
+    ("obin", RelationKind::Broader, Some("broaderTermInstantial")),
+    ("obpa", RelationKind::Broader, Some("broaderTermPartitive")),
+    ("adue", RelationKind::Broader, Some("hierarchicalSuperior")),
+    ("vbal", RelationKind::Related, Some("relatedTerm")),
+    ("rela", RelationKind::Related, Some("relatedTerm")),
+    (
+        "beza",
+        RelationKind::Related,
+        Some("acquaintanceshipOrFriendship"),
+    ),
+    (
+        "bezb",
+        RelationKind::Related,
+        Some("professionalRelationship"),
+    ),
+    ("bezf", RelationKind::Related, Some("familialRelationship")),
+    ("affi", RelationKind::Related, Some("affiliation")),
+    ("grue", RelationKind::Related, Some("founder")),
+    ("pseu", RelationKind::Related, Some("pseudonym")),
+    ("nawi", RelationKind::Related, Some("realIdentity")),
+    ("vorg", RelationKind::Related, None),
+    ("nach", RelationKind::Related, None),
+    ("ortg", RelationKind::Related, Some("placeOfBirth")),
+    ("orts", RelationKind::Related, Some("placeOfDeath")),
+    ("ortw", RelationKind::Related, Some("placeOfActivity")),
+    ("orta", RelationKind::Related, Some("placeOfBusiness")),
+    ("geow", RelationKind::Related, None),
+    (
+        "beru",
+        RelationKind::Related,
+        Some("professionOrOccupation"),
+    ),
+    (
+        "berc",
+        RelationKind::Related,
+        Some("professionOrOccupation"),
+    ),
+    ("stud", RelationKind::Related, Some("fieldOfStudy")),
+    ("them", RelationKind::Related, Some("topic")),
+    ("aut1", RelationKind::Related, Some("firstAuthor")),
+    ("auta", RelationKind::Related, Some("author")),
+    ("kom1", RelationKind::Related, Some("firstComposer")),
+    ("koma", RelationKind::Related, Some("composer")),
+    ("kue1", RelationKind::Related, Some("firstArtist")),
+    ("kuen", RelationKind::Related, Some("artist")),
+];
+
+/// Relation codes of symmetric relations, i.e. the inverse relation has
+/// the same meaning as the relation itself.
+const SYMMETRIC_CODES: [&str; 5] = ["vbal", "rela", "beza", "bezb", "bezf"];
+
+#[derive(Debug, Clone)]
 pub struct Relation {
     pub(crate) uri: String,
-    kind: RelationKind,
+    pub(crate) kind: RelationKind,
+    pub(crate) code: String,
+    pub(crate) field: String,
+    pub(crate) idn: String,
 }
 
 impl Relation {
-    pub fn new<S: Into<String>>(uri: S, kind: RelationKind) -> Self {
+    pub fn new<S: Into<String>>(uri: S, kind: RelationKind, code: S) -> Self {
         Self {
             uri: uri.into(),
             kind,
+            code: code.into(),
+            field: String::new(),
+            idn: String::new(),
         }
     }
 
@@ -24,6 +121,82 @@ impl Relation {
     pub fn kind(&self) -> &RelationKind {
         &self.kind
     }
+
+    /// Returns the relation code (`$4`) of the relation.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the IDN (`$9`) of the target concept.
+    pub fn idn(&self) -> &str {
+        &self.idn
+    }
+
+    /// Returns the tag of the PICA+ field the relation was built from.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the entity kind of the target concept, which is derived from
+    /// the tag of the field the relation was built from.
+    pub fn target_kind(&self) -> Option<ConceptKind> {
+        match self.field.as_str() {
+            "022R" => Some(ConceptKind::Work),
+            "028R" => Some(ConceptKind::Person),
+            "029R" => Some(ConceptKind::CorporateBody),
+            "030R" => Some(ConceptKind::Conference),
+            "041R" => Some(ConceptKind::SubjectTerm),
+            "065R" => Some(ConceptKind::Place),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the relation code denotes a symmetric relation
+    /// (e.g. `vbal`, `beza`).
+    pub fn is_symmetric(&self) -> bool {
+        SYMMETRIC_CODES.contains(&self.code.as_str())
+    }
+
+    /// Returns the GND Ontology property of the relation code, if there is
+    /// one.
+    pub fn gndo_property(&self) -> Option<String> {
+        RELATION_CODES
+            .iter()
+            .find(|(code, _, _)| *code == self.code)
+            .and_then(|(_, _, property)| *property)
+            .map(|property| String::from(GNDO_NS) + property)
+    }
+
+    /// Returns the predicate URI of the relation in the GND Ontology
+    /// profile. A mapping of the relation code in the `[relation]` config
+    /// takes precedence over the GND Ontology property of the code.
+    /// Relations without such a property fall back to the SKOS property of
+    /// the relation kind.
+    pub fn gndo_predicate(&self, config: &Config) -> String {
+        if config.relation.mapping.contains_key(&self.code) {
+            return self.predicate(config);
+        }
+
+        self.gndo_property()
+            .unwrap_or_else(|| self.predicate(config))
+    }
+
+    /// Returns the predicate URI of the relation. A mapping of the relation
+    /// code in the `[relation]` config takes precedence over the SKOS
+    /// property of the relation kind.
+    pub fn predicate(&self, config: &Config) -> String {
+        if let Some(predicate) = config.relation.mapping.get(&self.code) {
+            return expand_predicate(predicate);
+        }
+
+        let name = match self.kind {
+            RelationKind::Broader => "broader",
+            RelationKind::Narrower => "narrower",
+            RelationKind::Related => "related",
+        };
+
+        String::from(SKOS_NS) + name
+    }
 }
 
 impl TryFrom<&Field> for Relation {
@@ -35,24 +208,43 @@ impl TryFrom<&Field> for Relation {
         }
 
         let idn = field.first('9').unwrap().to_string();
-        let kind =
-            RelationKind::from_str(&field.first('4').unwrap().to_string())
-                .unwrap();
+        let code = field.first('4').unwrap().to_string();
+        let kind = RelationKind::from_str(&code).unwrap();
 
-        Ok(Relation::new(idn, kind))
+        Ok(Relation {
+            field: field.tag().to_string(),
+            idn: idn.clone(),
+            ..Relation::new(idn, kind, code)
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RelationKind {
     Broader,
     Narrower,
     Related,
 }
 
+impl fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Broader => write!(f, "broader"),
+            Self::Narrower => write!(f, "narrower"),
+            Self::Related => write!(f, "related"),
+        }
+    }
+}
+
 impl FromStr for RelationKind {
     type Err = Infallible;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, kind, _)) =
+            RELATION_CODES.iter().find(|(code, _, _)| *code == s)
+        {
+            return Ok(*kind);
+        }
+
         Ok(if s.starts_with("ob") {
             RelationKind::Broader
         } else {
@@ -60,3 +252,71 @@ impl FromStr for RelationKind {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relation_kind_from_str() {
+        assert_eq!(RelationKind::from_str("obin"), Ok(RelationKind::Broader));
+        assert_eq!(RelationKind::from_str("adue"), Ok(RelationKind::Broader));
+        assert_eq!(RelationKind::from_str("vbal"), Ok(RelationKind::Related));
+        assert_eq!(RelationKind::from_str("obxx"), Ok(RelationKind::Broader));
+        assert_eq!(RelationKind::from_str("xxxx"), Ok(RelationKind::Related));
+        assert_eq!(RelationKind::Narrower.to_string(), "narrower");
+    }
+
+    #[test]
+    fn test_is_valid_predicate() {
+        assert!(is_valid_predicate("skos:related"));
+        assert!(is_valid_predicate("gndo:acquaintanceshipOrFriendship"));
+        assert!(is_valid_predicate("http://example.org/part"));
+        assert!(!is_valid_predicate("part"));
+        assert!(!is_valid_predicate("http://example.org/part of"));
+        assert!(!is_valid_predicate("<http://example.org/part>"));
+    }
+
+    #[test]
+    fn test_relation_predicate() {
+        let mut config = Config::default();
+        config
+            .relation
+            .mapping
+            .insert("beza".to_string(), "gndo:acquaintance".to_string());
+        config
+            .relation
+            .mapping
+            .insert("obpa".to_string(), "http://example.org/part".to_string());
+
+        let relation = Relation::new("1", RelationKind::Broader, "obin");
+        assert_eq!(relation.predicate(&config), format!("{}broader", SKOS_NS));
+        assert_eq!(
+            relation.gndo_property(),
+            Some(format!("{}broaderTermInstantial", GNDO_NS))
+        );
+
+        let relation = Relation::new("1", RelationKind::Related, "beza");
+        assert_eq!(
+            relation.predicate(&config),
+            format!("{}acquaintance", GNDO_NS)
+        );
+
+        let relation = Relation::new("1", RelationKind::Broader, "obpa");
+        assert_eq!(relation.predicate(&config), "http://example.org/part");
+        assert_eq!(relation.gndo_predicate(&config), "http://example.org/part");
+
+        let relation = Relation::new("1", RelationKind::Related, "vorg");
+        assert_eq!(relation.gndo_property(), None);
+        assert_eq!(
+            relation.gndo_predicate(&config),
+            format!("{}related", SKOS_NS)
+        );
+
+        let relation = Relation::new("1", RelationKind::Related, "berc");
+        assert_eq!(
+            relation.gndo_predicate(&config),
+            format!("{}professionOrOccupation", GNDO_NS)
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use crate::concept::ConceptBuilder;
 use crate::config::TranslitChoice;
-use crate::{Concept, ConceptKind, Config, Result, SynKind, Synonym};
+use crate::{
+    language, place, Concept, ConceptKind, Config, Result, SynKind, Synonym,
+};
 use pica::StringRecord;
 use pica_core::Field;
+use regex::Regex;
 
 pub(crate) struct SubjectTermBuilder;
 
@@ -11,12 +14,14 @@ fn get_synonym(
     kind: SynKind,
     translit: Option<&TranslitChoice>,
     min_length: usize,
-    synonym_filter: Option<&String>,
+    synonym_filter: Option<&Regex>,
 ) -> Option<Synonym> {
     let mut synonym = Synonym::builder(kind)
         .translit(translit)
         .min_length(min_length)
-        .filter(synonym_filter);
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field));
 
     if field.contains_code('a') {
         for subfield in field.iter() {
@@ -47,11 +52,14 @@ impl ConceptBuilder for SubjectTermBuilder {
         let mut concept =
             Concept::new(uri, relations, ConceptKind::SubjectTerm);
         let min_length = config.concept.min_synonym_length.unwrap_or_default();
-        let synonym_filter = config.concept.synonym_filter.as_ref();
+        let synonym_filter = config.concept.synonym_filter.as_deref();
         let translit = config.concept.translit.as_ref();
+        let strip_qualifiers =
+            config.concept.strip_qualifiers.unwrap_or_default();
 
+        let field = Self::required_field(record, "041A")?;
         if let Some(synonym) = get_synonym(
-            record.first("041A").unwrap(),
+            field,
             SynKind::Preferred,
             translit,
             min_length,
@@ -60,6 +68,8 @@ impl ConceptBuilder for SubjectTermBuilder {
             concept.add_synonym(synonym);
         }
 
+        let mut fields = vec![field];
+
         for field in record.all("041@").unwrap_or_default() {
             if let Some(synonym) = get_synonym(
                 field,
@@ -70,6 +80,23 @@ impl ConceptBuilder for SubjectTermBuilder {
             ) {
                 concept.add_synonym(synonym);
             }
+
+            fields.push(field);
+        }
+
+        if strip_qualifiers {
+            for field in fields {
+                if let Some(synonym) = place::get_unqualified_synonym(
+                    field,
+                    &['g'],
+                    |name| name,
+                    translit,
+                    min_length,
+                    synonym_filter,
+                ) {
+                    concept.add_synonym_if_new(synonym);
+                }
+            }
         }
 
         Ok(concept)
@@ -0,0 +1,198 @@
+use std::ops::Deref;
+
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::synset::SynonymBuilder;
+use crate::{ConceptKind, Config, Error, Result, SynKind, Synonym};
+
+/// A regular expression, which is compiled when the config is loaded.
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        Regex::new(pattern)
+            .map(Pattern)
+            .map_err(|e| Error::InvalidRegex {
+                pattern: pattern.to_owned(),
+                source: e,
+            })
+    }
+}
+
+impl Deref for Pattern {
+    type Target = Regex;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Pattern::new(&pattern).map_err(de::Error::custom)
+    }
+}
+
+/// A rule, which derives a new synonym from the synonyms of a concept.
+///
+/// ```toml
+/// [[synonym_rule]]
+/// kinds = ["person"]
+/// apply_to = ["preferred", "alternative"]
+/// pattern = '^(.+) \([^)]+\)$'
+/// replacement = "$1"
+/// target = "hidden"
+/// ```
+#[derive(Deserialize, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SynonymRule {
+    /// The entity kinds the rule applies to (all kinds, if empty).
+    #[serde(default)]
+    pub kinds: Vec<ConceptKind>,
+    /// The kinds of synonyms the rule applies to (all kinds, if empty).
+    #[serde(default)]
+    pub apply_to: Vec<SynKind>,
+    pub pattern: Pattern,
+    /// The replacement of the match, which may refer to capture groups
+    /// (`$1`, `${name}`).
+    pub replacement: String,
+    /// The kind of the derived synonym: "alternative" or "hidden". A
+    /// concept has only one preferred label, so "preferred" is rejected.
+    #[serde(
+        default = "default_target",
+        deserialize_with = "deserialize_target"
+    )]
+    pub target: SynKind,
+}
+
+fn default_target() -> SynKind {
+    SynKind::Hidden
+}
+
+fn deserialize_target<'de, D>(
+    deserializer: D,
+) -> std::result::Result<SynKind, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match SynKind::deserialize(deserializer)? {
+        SynKind::Preferred => Err(de::Error::custom(
+            "the target of a synonym rule must be 'alternative' or 'hidden'",
+        )),
+        kind => Ok(kind),
+    }
+}
+
+impl SynonymRule {
+    /// Returns `true` if the rule applies to concepts of the given kind.
+    pub fn applies_to(&self, kind: &ConceptKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(kind)
+    }
+
+    /// Returns the synonym derived from the given synonym, if the synonym
+    /// matches the rule and the result differs from the original label. The
+    /// synonym is built with the label settings of the config.
+    pub fn apply(&self, synonym: &Synonym, config: &Config) -> Option<Synonym> {
+        if !self.apply_to.is_empty() && !self.apply_to.contains(synonym.kind())
+        {
+            return None;
+        }
+
+        if !self.pattern.is_match(synonym.label()) {
+            return None;
+        }
+
+        let label = self
+            .pattern
+            .replace(synonym.label(), self.replacement.as_str());
+        let label = label.trim();
+
+        if label.is_empty() || label == synonym.label() {
+            return None;
+        }
+
+        SynonymBuilder::new(self.target.clone())
+            .with_config(config)
+            .source(synonym.source())
+            .language(synonym.language())
+            .push_str(label)
+            .build()
+            .filter(|derived| derived.label() != synonym.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synonym_rule() {
+        let rule: SynonymRule = toml::from_str(
+            r#"
+            kinds = ["place"]
+            pattern = '^(.+) \([^)]+\)$'
+            replacement = "$1"
+            "#,
+        )
+        .unwrap();
+
+        assert!(rule.applies_to(&ConceptKind::Place));
+        assert!(!rule.applies_to(&ConceptKind::Person));
+
+        let mut config = Config::default();
+        let synonym = Synonym::new("Frankfurt (Oder)", SynKind::Preferred);
+        let result = rule.apply(&synonym, &config).unwrap();
+        assert_eq!(result.label(), "Frankfurt");
+        assert_eq!(result.kind(), &SynKind::Hidden);
+
+        let synonym = Synonym::new("Frankfurt", SynKind::Preferred);
+        assert!(rule.apply(&synonym, &config).is_none());
+
+        // The derived synonym has to pass the filters of the config.
+        config.concept.min_synonym_length = Some(10);
+        let synonym = Synonym::new("Frankfurt (Oder)", SynKind::Preferred);
+        assert!(rule.apply(&synonym, &config).is_none());
+
+        let rule: std::result::Result<SynonymRule, _> = toml::from_str(
+            r#"
+            pattern = '^(.+) \([^)]+\)$'
+            replacement = "$1"
+            target = "preferred"
+            "#,
+        );
+        assert!(rule.is_err());
+    }
+
+    #[test]
+    fn test_pattern() {
+        assert!(Pattern::new(r"\(.*Motiv\)$").unwrap().is_match("X (Motiv)"));
+        assert!(matches!(
+            Pattern::new("(Motiv"),
+            Err(Error::InvalidRegex { .. })
+        ));
+
+        let config: std::result::Result<crate::config::ConceptConfig, _> =
+            toml::from_str(
+                r#"
+                base_uri = "http://d-nb.info/gnd/"
+                skip_invalid = true
+                synonym_filter = "(Motiv"
+                "#,
+            );
+        assert!(config.is_err());
+    }
+}
@@ -1,16 +1,25 @@
 use std::collections::HashSet;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use bstr::BString;
 use regex::Regex;
+use serde::Deserialize;
 use unicode_normalization::UnicodeNormalization;
 
-use crate::config::TranslitChoice;
+use crate::config::{TranslitChoice, TranslitVariant};
+use crate::{language, translit, Config};
 
-#[derive(Debug, PartialEq, Hash, Eq)]
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[derive(Debug)]
 pub struct Synonym {
     label: String,
     kind: SynKind,
+    source: Option<String>,
+    language: Option<String>,
+    unqualified: bool,
 }
 
 impl Synonym {
@@ -21,6 +30,9 @@ impl Synonym {
         Self {
             label: label.into(),
             kind,
+            source: None,
+            language: None,
+            unqualified: false,
         }
     }
 
@@ -35,6 +47,87 @@ impl Synonym {
     pub fn kind(&self) -> &SynKind {
         &self.kind
     }
+
+    /// Returns the tag of the PICA+ field the synonym was built from.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Returns the language tag stated by the record (`$L`, `$U`).
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Returns `true` if the synonym is a heading without its qualifiers
+    /// (e.g. "Berlin" for "Berlin (West)").
+    pub fn is_unqualified(&self) -> bool {
+        self.unqualified
+    }
+
+    /// Returns the BCP-47 language tag of the synonym. If the record doesn't
+    /// state the language, it is guessed from the script of the label.
+    pub fn language_tag(&self) -> Option<&str> {
+        self.language().or_else(|| language::detect(&self.label))
+    }
+
+    /// Returns a hidden label for each transliteration (`translit_variants`)
+    /// of the label, which differs from the label itself. The labels are
+    /// built with the label settings of the config; romanized labels are
+    /// tagged with the Latin script.
+    pub fn transliterations(&self, config: &Config) -> Vec<Synonym> {
+        config
+            .concept
+            .translit_variants
+            .iter()
+            .filter_map(|variant| {
+                let label = translit::transliterate(&self.label, variant);
+
+                let language = match variant {
+                    TranslitVariant::Romanize => {
+                        self.language_tag().map(|tag| {
+                            let language = tag.split('-').next().unwrap_or(tag);
+                            format!("{}-Latn", language)
+                        })
+                    }
+                    _ => self.language.clone(),
+                };
+
+                SynonymBuilder::new(SynKind::Hidden)
+                    .with_config(config)
+                    .source(self.source())
+                    .language(language)
+                    .push_str(label)
+                    .build()
+                    .filter(|synonym| synonym.label != self.label)
+            })
+            .collect()
+    }
+
+    /// Returns a FNV-1a hash of the kind and label of the synonym. Unlike
+    /// the `Hash` implementation, the value is stable across releases and
+    /// platforms and can be used to derive identifiers.
+    pub fn fingerprint(&self) -> u64 {
+        format!("{}:{}", self.kind, self.label)
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            })
+    }
+}
+
+impl PartialEq for Synonym {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && self.kind == other.kind
+    }
+}
+
+impl Eq for Synonym {}
+
+impl Hash for Synonym {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.label.hash(state);
+        self.kind.hash(state);
+    }
 }
 
 #[derive(Clone)]
@@ -44,6 +137,9 @@ pub struct SynonymBuilder {
     translit: Option<TranslitChoice>,
     min_length: usize,
     filter: Option<Regex>,
+    source: Option<String>,
+    language: Option<String>,
+    unqualified: bool,
 }
 
 impl SynonymBuilder {
@@ -55,6 +151,9 @@ impl SynonymBuilder {
             translit: None,
             min_length: 0,
             filter: None,
+            source: None,
+            language: None,
+            unqualified: false,
         }
     }
 
@@ -72,8 +171,32 @@ impl SynonymBuilder {
         self.min_length = min_length;
         self
     }
-    pub fn filter(mut self, filter: Option<&String>) -> Self {
-        self.filter = filter.map(|s| Regex::new(s).unwrap());
+
+    pub fn filter(mut self, filter: Option<&Regex>) -> Self {
+        self.filter = filter.cloned();
+        self
+    }
+
+    /// Applies the label settings of the config: the Unicode normalization
+    /// (`translit`), `min_synonym_length` and `synonym_filter`.
+    pub(crate) fn with_config(self, config: &Config) -> Self {
+        self.translit(config.concept.translit.as_ref())
+            .min_length(config.concept.min_synonym_length.unwrap_or_default())
+            .filter(config.concept.synonym_filter.as_deref())
+    }
+
+    pub fn source<S: AsRef<str>>(mut self, source: Option<S>) -> Self {
+        self.source = source.map(|s| s.as_ref().to_string());
+        self
+    }
+
+    pub fn language<S: AsRef<str>>(mut self, language: Option<S>) -> Self {
+        self.language = language.map(|s| s.as_ref().to_string());
+        self
+    }
+
+    pub fn unqualified(mut self, unqualified: bool) -> Self {
+        self.unqualified = unqualified;
         self
     }
 
@@ -134,7 +257,13 @@ impl SynonymBuilder {
                 }
             }
 
-            Some(Synonym::new(&label, self.kind))
+            Some(Synonym {
+                label,
+                kind: self.kind,
+                source: self.source,
+                language: self.language,
+                unqualified: self.unqualified,
+            })
         } else {
             None
         }
@@ -143,11 +272,15 @@ impl SynonymBuilder {
 
 impl From<&Synonym> for SynonymBuilder {
     fn from(synonym: &Synonym) -> Self {
-        SynonymBuilder::new(synonym.kind().to_owned()).push_str(synonym.label())
+        SynonymBuilder::new(synonym.kind().to_owned())
+            .source(synonym.source())
+            .language(synonym.language())
+            .push_str(synonym.label())
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SynKind {
     Preferred,
     Alternative,
@@ -176,4 +309,63 @@ mod tests {
         assert_eq!(SynKind::Alternative.to_string(), "alternative");
         assert_eq!(SynKind::Hidden.to_string(), "hidden");
     }
+
+    #[test]
+    fn test_synonym_fingerprint() {
+        let synonym = Synonym::new("Broch, Hermann", SynKind::Hidden);
+        assert_ne!(
+            synonym.fingerprint(),
+            Synonym::new("Broch, Hermann", SynKind::Alternative).fingerprint()
+        );
+        assert_eq!(synonym.fingerprint(), 0x936fa96783b6d272);
+    }
+
+    #[test]
+    fn test_synonym_eq_ignores_source() {
+        let lhs = Synonym::builder(SynKind::Alternative)
+            .source(Some("028@"))
+            .push_str("Broch, Herman")
+            .build()
+            .unwrap();
+        let rhs = Synonym::new("Broch, Herman", SynKind::Alternative);
+
+        assert_eq!(lhs.source(), Some("028@"));
+        assert_eq!(lhs, rhs);
+    }
+
+    #[test]
+    fn test_synonym_transliterations() {
+        let mut config = Config::default();
+        config.concept.translit_variants =
+            vec![TranslitVariant::Ascii, TranslitVariant::Romanize];
+
+        let synonym = Synonym::new("Брох, Герман", SynKind::Alternative);
+        let variants = synonym.transliterations(&config);
+
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].label(), "Brokh, German");
+        assert_eq!(variants[0].kind(), &SynKind::Hidden);
+        assert_eq!(variants[0].language(), Some("und-Latn"));
+
+        // The variants are normalized like all other labels.
+        config.concept.translit = Some(TranslitChoice::Nfd);
+        config.concept.translit_variants = vec![TranslitVariant::Umlaut];
+
+        let synonym = Synonym::new("Müller, José", SynKind::Preferred);
+        let variants = synonym.transliterations(&config);
+        assert_eq!(
+            variants[0].label(),
+            "Mueller, José".nfd().collect::<String>()
+        );
+
+        let synonym = "Sokolovskiĭ, Ivan".nfd().collect::<String>();
+        let synonym = Synonym::new(synonym, SynKind::Preferred);
+        assert!(synonym.transliterations(&config).is_empty());
+
+        // Variants, which don't pass the filters, are dropped.
+        config.concept.translit_variants = vec![TranslitVariant::Ascii];
+        config.concept.min_synonym_length = Some(10);
+        let synonym = Synonym::new("Łódź", SynKind::Preferred);
+        assert!(synonym.transliterations(&config).is_empty());
+    }
 }
@@ -0,0 +1,204 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::TranslitVariant;
+
+/// Latin letters, which don't decompose into a base letter and a
+/// combining mark.
+const LATIN_LIGATURES: [(char, &str); 16] = [
+    ('ß', "ss"),
+    ('ẞ', "SS"),
+    ('æ', "ae"),
+    ('Æ', "AE"),
+    ('œ', "oe"),
+    ('Œ', "OE"),
+    ('ø', "o"),
+    ('Ø', "O"),
+    ('ł', "l"),
+    ('Ł', "L"),
+    ('đ', "d"),
+    ('Đ', "D"),
+    ('þ', "th"),
+    ('Þ', "Th"),
+    ('ı', "i"),
+    ('ð', "d"),
+];
+
+const GERMAN_UMLAUTS: [(char, &str); 7] = [
+    ('ä', "ae"),
+    ('ö', "oe"),
+    ('ü', "ue"),
+    ('Ä', "Ae"),
+    ('Ö', "Oe"),
+    ('Ü', "Ue"),
+    ('ß', "ss"),
+];
+
+/// Romanization of the (lowercase) Cyrillic and Greek letters.
+const ROMANIZATION: [(char, &str); 61] = [
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('ґ', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('є', "ye"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('і', "i"),
+    ('ї', "yi"),
+    ('й', "y"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+    ('α', "a"),
+    ('β', "v"),
+    ('γ', "g"),
+    ('δ', "d"),
+    ('ε', "e"),
+    ('ζ', "z"),
+    ('η', "i"),
+    ('θ', "th"),
+    ('ι', "i"),
+    ('κ', "k"),
+    ('λ', "l"),
+    ('μ', "m"),
+    ('ν', "n"),
+    ('ξ', "x"),
+    ('ο', "o"),
+    ('π', "p"),
+    ('ρ', "r"),
+    ('σ', "s"),
+    ('ς', "s"),
+    ('τ', "t"),
+    ('υ', "y"),
+    ('φ', "f"),
+    ('χ', "ch"),
+    ('ψ', "ps"),
+    ('ω', "o"),
+];
+
+fn lookup(table: &[(char, &'static str)], c: char) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(key, _)| *key == c)
+        .map(|(_, value)| *value)
+}
+
+/// Removes all diacritics and replaces the remaining non-ASCII Latin
+/// letters (e.g. "Müller" → "Muller", "Łódź" → "Lodz").
+fn fold_ascii(label: &str) -> String {
+    let mut result = String::with_capacity(label.len());
+
+    for c in label.nfd().filter(|c| !is_combining_mark(*c)) {
+        match lookup(&LATIN_LIGATURES, c) {
+            Some(value) => result.push_str(value),
+            None => result.push(c),
+        }
+    }
+
+    result.nfc().collect()
+}
+
+/// Expands the German umlauts (e.g. "Müller" → "Mueller").
+fn expand_umlauts(label: &str) -> String {
+    let mut result = String::with_capacity(label.len());
+
+    for c in label.nfc() {
+        match lookup(&GERMAN_UMLAUTS, c) {
+            Some(value) => result.push_str(value),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Romanizes Cyrillic and Greek letters (e.g. "Брох, Герман" → "Brokh,
+/// German"). Letters of other scripts are kept.
+fn romanize(label: &str) -> String {
+    let mut result = String::with_capacity(label.len());
+
+    for c in label.nfc() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+
+        // Letters like "й" or "ї" have their own romanization, other
+        // letters (e.g. "ά") are looked up without their accents.
+        let value = lookup(&ROMANIZATION, lower).or_else(|| {
+            lower.to_string().nfd().next().and_then(|base| {
+                if base != lower {
+                    lookup(&ROMANIZATION, base)
+                } else {
+                    None
+                }
+            })
+        });
+
+        match value {
+            Some(value) if lower != c => {
+                let mut chars = value.chars();
+                if let Some(first) = chars.next() {
+                    result.extend(first.to_uppercase());
+                    result.extend(chars);
+                }
+            }
+            Some(value) => result.push_str(value),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Returns the transliteration of the label.
+pub(crate) fn transliterate(label: &str, variant: &TranslitVariant) -> String {
+    match variant {
+        TranslitVariant::Ascii => fold_ascii(label),
+        TranslitVariant::Umlaut => expand_umlauts(label),
+        TranslitVariant::Romanize => romanize(label),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate() {
+        let ascii = TranslitVariant::Ascii;
+        assert_eq!(transliterate("Müller", &ascii), "Muller");
+        assert_eq!(transliterate("Łódź", &ascii), "Lodz");
+        assert_eq!(transliterate("Broch", &ascii), "Broch");
+
+        let umlaut = TranslitVariant::Umlaut;
+        assert_eq!(transliterate("Müller", &umlaut), "Mueller");
+        assert_eq!(transliterate("Straße", &umlaut), "Strasse");
+
+        let romanize = TranslitVariant::Romanize;
+        assert_eq!(transliterate("Брох, Герман", &romanize), "Brokh, German");
+        assert_eq!(transliterate("Щукин", &romanize), "Shchukin");
+        assert_eq!(transliterate("Σωκράτης", &romanize), "Sokratis");
+        assert_eq!(transliterate("Їжак", &romanize), "Yizhak");
+    }
+}
@@ -2,12 +2,13 @@ use crate::concept::ConceptBuilder;
 use crate::config::TranslitChoice;
 use crate::synset::SynonymBuilder;
 use crate::{
-    conference, corporate_body, person, place, Concept, ConceptKind, Config,
-    Result, SynKind, Synonym,
+    conference, corporate_body, language, person, place, Concept, ConceptKind,
+    Config, Result, SynKind, Synonym,
 };
 use pica::matcher::{MatcherFlags, SubfieldMatcher};
 use pica::StringRecord;
 use pica_core::Field;
+use regex::Regex;
 
 pub(crate) struct WorkBuilder;
 
@@ -16,12 +17,14 @@ fn get_synonym(
     kind: SynKind,
     translit: Option<&TranslitChoice>,
     min_length: usize,
-    synonym_filter: Option<&String>,
+    synonym_filter: Option<&Regex>,
 ) -> Option<Synonym> {
     let mut synonym = Synonym::builder(kind)
         .translit(translit)
         .min_length(min_length)
-        .filter(synonym_filter);
+        .filter(synonym_filter)
+        .source(Some(field.tag().to_string()))
+        .language(language::from_field(field));
 
     for subfield in field.iter() {
         let value = subfield.value().to_string();
@@ -107,7 +110,7 @@ fn get_prefix(record: &StringRecord) -> Option<String> {
 impl ConceptBuilder for WorkBuilder {
     fn from_record(record: &StringRecord, config: &Config) -> Result<Concept> {
         let min_length = config.concept.min_synonym_length.unwrap_or_default();
-        let synonym_filter = config.concept.synonym_filter.as_ref();
+        let synonym_filter = config.concept.synonym_filter.as_deref();
         let translit = config.concept.translit.as_ref();
 
         let mut concept = Concept::new(
@@ -117,7 +120,7 @@ impl ConceptBuilder for WorkBuilder {
         );
 
         if let Some(synonym) = get_synonym(
-            record.first("022A").unwrap(),
+            Self::required_field(record, "022A")?,
             SynKind::Preferred,
             translit,
             min_length,
@@ -125,6 +128,8 @@ impl ConceptBuilder for WorkBuilder {
         ) {
             if let Some(prefix) = get_prefix(record) {
                 if let Some(synonym) = SynonymBuilder::new(SynKind::Preferred)
+                    .source(synonym.source())
+                    .language(synonym.language())
                     .push_str(&format!("{} : {}", prefix, synonym.label()))
                     .build()
                 {
@@ -146,6 +151,8 @@ impl ConceptBuilder for WorkBuilder {
                 if let Some(prefix) = get_prefix(record) {
                     if let Some(synonym) =
                         SynonymBuilder::new(SynKind::Alternative)
+                            .source(synonym.source())
+                            .language(synonym.language())
                             .push_str(&format!(
                                 "{} : {}",
                                 prefix,